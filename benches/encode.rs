@@ -0,0 +1,37 @@
+//! Compares encoding a batch of requests one after another against spreading it across cores with [`EncodePar`].
+//!
+//! Run with `cargo bench --features rayon`. The speedup depends on the number of cores, so report it with the core
+//! count of the machine it ran on.
+#![allow(non_snake_case)]
+use criterion::{criterion_group, criterion_main, Criterion};
+use MoneroRequest_Rust::{EncodePar, EncodePaymentRequest, MoneroRequest};
+
+const BATCH_SIZE: usize = 1_000;
+
+fn Batch() -> Vec<MoneroRequest> {
+	(0..BATCH_SIZE).map(|Index| MoneroRequest {
+		CustomLabel: format!("Merchant {Index}"),
+		SellersWallet: "4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S".to_string(),
+		Currency: "USD".to_string(),
+		Amount: "1,234.56".to_string(),
+		PaymentID: "".to_string(),
+		StartDate: "2024-01-01T00:00:00.000Z".to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 12,
+		ChangeIndicatorURL: "https://example.com/changes".to_string(),
+		Version: "1".to_string()
+	}).collect()
+}
+
+fn EncodeBatch(c: &mut Criterion) {
+	let Requests = Batch();
+	let mut Group = c.benchmark_group("encode_batch");
+
+	Group.bench_function("sequential", |b| b.iter(|| Requests.iter().map(|Request| EncodePaymentRequest(Request.clone())).collect::<Vec<_>>()));
+	Group.bench_function("EncodePar", |b| b.iter(|| EncodePar(&Requests)));
+
+	Group.finish();
+}
+
+criterion_group!(benches, EncodeBatch);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+//! Decodes a Monero Payment Request read from stdin and prints its fields.
+//!
+//! Usage: `echo monero-request:1:... | cargo run --example decode [-- --show-wallet]`
+//!
+//! The seller wallet is redacted unless `--show-wallet` is passed.
+#![allow(non_snake_case)]
+use std::io::Read;
+use MoneroRequest_Rust::{DecodePaymentRequest, MaskAddress, MoneroRequestError};
+
+fn main() {
+	let ShowWallet = std::env::args().any(|A| A == "--show-wallet");
+
+	let mut Input = String::new();
+	if let Err(e) = std::io::stdin().read_to_string(&mut Input) {
+		eprintln!("Error reading stdin: {e}");
+		std::process::exit(1);
+	}
+
+	let Request = match DecodePaymentRequest(Input.trim().to_string()) {
+		Ok(r) => r,
+		Err(e) => {
+			match e {
+				MoneroRequestError::InvalidInput(e) => eprintln!("Not a valid Monero Payment Request: {e}"),
+				MoneroRequestError::Base64Error(e) => eprintln!("The request body is not valid base64: {e}"),
+				MoneroRequestError::GZipError(e) => eprintln!("The request body could not be decompressed: {e}"),
+				MoneroRequestError::SerdeError(e) => eprintln!("The request payload is not a valid request: {e}"),
+				e => eprintln!("Error decoding request: {e}")
+			}
+			std::process::exit(1);
+		}
+	};
+
+	let Wallet = match ShowWallet {
+		true => Request.SellersWallet.clone(),
+		false => format!("{} (pass --show-wallet to display)", MaskAddress(&Request.SellersWallet))
+	};
+
+	println!("Custom Label:           {}", Request.CustomLabel);
+	println!("Seller's Wallet:        {Wallet}");
+	println!("Currency:               {}", Request.Currency);
+	println!("Amount:                 {}", Request.Amount);
+	println!("Payment ID:             {}", Request.PaymentID);
+	println!("Start Date:             {}", Request.StartDate);
+	println!("Days Per Billing Cycle: {}", Request.DaysPerBillingCycle);
+	println!("Number Of Payments:     {}", Request.NumberOfPayments);
+	println!("Change Indicator URL:   {}", Request.ChangeIndicatorURL);
+	println!("Version:                {}", Request.Version);
+}
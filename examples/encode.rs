@@ -0,0 +1,44 @@
+//! Encodes a one-time Monero Payment Request from the command line.
+//!
+//! Usage: `cargo run --example encode -- <wallet> <amount> <currency>`
+//!
+//! With the `qr` feature enabled the request is also printed as a QR code.
+#![allow(non_snake_case)]
+use MoneroRequest_Rust::{EncodePaymentRequest, MoneroRequest};
+
+fn main() {
+	let Args: Vec<String> = std::env::args().skip(1).collect();
+	if Args.len() != 3 {
+		eprintln!("Usage: encode <wallet> <amount> <currency>");
+		std::process::exit(2);
+	}
+
+	let Request = MoneroRequest {
+		CustomLabel: "".to_string(),
+		SellersWallet: Args[0].clone(),
+		Currency: Args[2].clone(),
+		Amount: Args[1].clone(),
+		PaymentID: "".to_string(),
+		StartDate: "".to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 1,
+		ChangeIndicatorURL: "".to_string(),
+		Version: "".to_string()
+	};
+
+	let Output = match EncodePaymentRequest(Request) {
+		Ok(r) => r,
+		Err(e) => {
+			eprintln!("Error encoding request: {e}");
+			std::process::exit(1);
+		}
+	};
+
+	println!("{Output}");
+
+	#[cfg(feature = "qr")]
+	match MoneroRequest_Rust::RenderQRCode(&Output) {
+		Ok(r) => println!("\n{r}"),
+		Err(e) => eprintln!("Error rendering QR code: {e}")
+	}
+}
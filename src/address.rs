@@ -0,0 +1,268 @@
+//! Monero wallet address decoding: Monero's block base58, the Keccak-256 checksum, and the network prefix table.
+//!
+//! The primitives are public for building other address logic on, e.g. constructing addresses. For checking and
+//! inspecting addresses prefer [`ValidateWalletAddress`], [`ParseWalletAddress`], and [`IntegratedPaymentID`], which
+//! are also at the crate root.
+use std::fmt;
+use tiny_keccak::{Hasher, Keccak};
+use crate::MoneroRequestError;
+
+
+
+/// The Monero network a wallet address belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+	Mainnet,
+	Testnet,
+	Stagenet
+}
+
+/// The kind of wallet address, as encoded in its network prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressType {
+	/// A primary (standard) address. 95 characters.
+	Standard,
+	/// A standard address with an embedded 8 byte payment id. 106 characters.
+	Integrated,
+	/// A subaddress derived from a wallet's primary address. 95 characters.
+	Subaddress
+}
+
+/// The characters of Monero's base58, in digit order. Unlike Bitcoin's it's applied in 8 byte blocks, see
+/// [`EncodeBase58`].
+pub const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Monero base58 encodes 8 byte blocks into 11 characters. The index is the number of bytes in a block, the value is
+// the number of characters it encodes to.
+const ENCODED_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
+/// The network prefix of each kind of address on each network. An address starts with its prefix as a varint.
+pub const PREFIXES: [(u64, Network, AddressType); 9] = [
+	(18, Network::Mainnet, AddressType::Standard),
+	(19, Network::Mainnet, AddressType::Integrated),
+	(42, Network::Mainnet, AddressType::Subaddress),
+	(53, Network::Testnet, AddressType::Standard),
+	(54, Network::Testnet, AddressType::Integrated),
+	(63, Network::Testnet, AddressType::Subaddress),
+	(24, Network::Stagenet, AddressType::Standard),
+	(25, Network::Stagenet, AddressType::Integrated),
+	(36, Network::Stagenet, AddressType::Subaddress)
+];
+
+const KEYS_LENGTH: usize = 64;
+const INTEGRATED_ID_LENGTH: usize = 8;
+/// The length in bytes of the checksum an address ends with, see [`Checksum`].
+pub const CHECKSUM_LENGTH: usize = 4;
+
+
+
+/// Checks that a wallet address is a well formed mainnet address with a valid checksum. This is the check
+/// [`EncodePaymentRequest`](crate::EncodePaymentRequest) applies to [`SellersWallet`](crate::MoneroRequest::SellersWallet).
+pub fn ValidateWalletAddress(Address: &str) -> Result<(), MoneroRequestError> {
+	ValidateWalletAddressOn(Address, false)
+}
+
+/// A check for [`SellersWallet`](crate::MoneroRequest::SellersWallet), for plugging a different address policy into
+/// validation with [`ValidationOptions::AddressValidator`](crate::ValidationOptions::AddressValidator), e.g. to accept
+/// an experimental address format. Without one the built-in [`ValidateWalletAddress`] is used.
+pub trait AddressValidator: fmt::Debug + Send + Sync {
+	/// Returns an error if `Address` isn't acceptable.
+	fn Validate(&self, Address: &str) -> Result<(), MoneroRequestError>;
+}
+
+// Same as ValidateWalletAddress, optionally also accepting testnet and stagenet addresses. Only reachable with the
+// `test-networks` feature, see ValidationOptions::AllowTestNetworks.
+pub(crate) fn ValidateWalletAddressOn(Address: &str, AllowTestNetworks: bool) -> Result<(), MoneroRequestError> {
+	match Address.len() {
+		0 => return Err(MoneroRequestError::InvalidInput("No seller wallet specified.")),
+		95 | 106 => {},
+		_ => return Err(MoneroRequestError::InvalidInput("Incorrect seller wallet address length."))
+	}
+
+	let FirstChar = Address.chars().next().unwrap();	// This unwrap should be safe as we checked lenth > 0 earlier
+	if !AllowTestNetworks && FirstChar != '4' && FirstChar != '8' {
+		return Err(MoneroRequestError::InvalidInput("Invalid wallet address. Doesnt start with 4 or 8."));
+	}
+	// Subaddresses start with an 8, but only standard addresses share their length. Integrated addresses always start
+	// with a 4.
+	if !AllowTestNetworks && Address.len() == 106 && FirstChar == '8' {
+		return Err(MoneroRequestError::InvalidInput("Invalid wallet address. Integrated addresses start with 4."));
+	}
+
+	// Base58 leaves out the lookalikes 0, O, I, and l, so one of those is almost certainly a mistyped character. Catching
+	// it here gives a clearer error than the checksum failure it would otherwise cause.
+	if !Address.bytes().all(|C| ALPHABET.contains(&C)) {
+		return Err(MoneroRequestError::InvalidInput("Invalid base58 character in wallet address"));
+	}
+
+	// Checksum and network prefix. Standard, integrated, and subaddresses each have their own prefix byte.
+	match ParseWalletAddress(Address) {
+		Ok((Network::Mainnet, _)) => Ok(()),
+		Ok(_) if AllowTestNetworks => Ok(()),
+		Ok(_) => Err(MoneroRequestError::InvalidInput("Wallet address is not a mainnet address.")),
+		Err(e) => Err(e)
+	}
+}
+
+/// Decodes a wallet address and verifies its checksum, returning the [`Network`] and [`AddressType`] its prefix
+/// identifies.
+///
+/// Subaddresses carry their own network prefix, so they verify the same way standard and integrated addresses do.
+pub fn ParseWalletAddress(Address: &str) -> Result<(Network, AddressType), MoneroRequestError> {
+	let Data = match DecodeBase58(Address) {
+		Some(r) => r,
+		None => return Err(MoneroRequestError::InvalidInput("Invalid base58 encoding in wallet address."))
+	};
+
+	if Data.len() < CHECKSUM_LENGTH { return Err(MoneroRequestError::InvalidInput("Incorrect seller wallet address length.")); }
+	let (Body, Expected) = Data.split_at(Data.len() - CHECKSUM_LENGTH);
+	if Checksum(Body) != *Expected {
+		return Err(MoneroRequestError::InvalidInput("Invalid wallet address checksum."));
+	}
+
+	let (Prefix, PrefixLength) = match DecodeVarint(Body) {
+		Some(r) => r,
+		None => return Err(MoneroRequestError::InvalidInput("Invalid wallet address prefix."))
+	};
+
+	let (Network, AddressType) = match PREFIXES.iter().find(|(P, _, _)| *P == Prefix) {
+		Some((_, N, T)) => (*N, *T),
+		None => return Err(MoneroRequestError::InvalidInput("Unknown wallet address prefix."))
+	};
+
+	let ExpectedLength = match AddressType {
+		AddressType::Integrated => KEYS_LENGTH + INTEGRATED_ID_LENGTH,
+		_ => KEYS_LENGTH
+	};
+	if Body.len() - PrefixLength != ExpectedLength {
+		return Err(MoneroRequestError::InvalidInput("Incorrect seller wallet address length."));
+	}
+
+	Ok((Network, AddressType))
+}
+
+/// Returns the payment id embedded in an integrated address as 16 lowercase hex characters, or `None` for standard
+/// addresses and subaddresses. The address is verified the same way as [`ParseWalletAddress`].
+pub fn IntegratedPaymentID(Address: &str) -> Result<Option<String>, MoneroRequestError> {
+	if ParseWalletAddress(Address)?.1 != AddressType::Integrated { return Ok(None); }
+
+	// These unwraps are safe as ParseWalletAddress already decoded the address and checked its length
+	let Data = DecodeBase58(Address).unwrap();
+	let (_, PrefixLength) = DecodeVarint(&Data).unwrap();
+	let Start = PrefixLength + KEYS_LENGTH;
+
+	let Output = Data[Start..Start + INTEGRATED_ID_LENGTH].iter().map(|B| format!("{B:02x}")).collect();
+
+	Ok(Some(Output))
+}
+
+// Returns true if the address decodes and its public spend or view key is all zeroes or the identity point (a 1 then
+// zeroes). Such an address passes its checksum but nobody holds its keys, so anything sent to it is burned.
+pub(crate) fn IsBurnAddress(Address: &str) -> bool {
+	let Data = match DecodeBase58(Address) {
+		Some(r) => r,
+		None => return false
+	};
+	let PrefixLength = match DecodeVarint(&Data) {
+		Some((_, r)) => r,
+		None => return false
+	};
+	if Data.len() < PrefixLength + KEYS_LENGTH { return false; }
+
+	let Null = |Key: &[u8]| Key[1..].iter().all(|B| *B == 0) && Key[0] <= 1;
+	let (Spend, View) = Data[PrefixLength..PrefixLength + KEYS_LENGTH].split_at(KEYS_LENGTH / 2);
+
+	Null(Spend) || Null(View)
+}
+
+/// Shortens an address for display by keeping its first and last 6 characters, e.g. `4At3X5…hCeX2S`. See
+/// [`MaskAddressWith`] to keep a different number.
+pub fn MaskAddress(Address: &str) -> String {
+	MaskAddressWith(Address, 6)
+}
+
+/// Same as [`MaskAddress`], but keeps `Keep` characters at each end. Addresses too short to shorten are returned
+/// unchanged.
+pub fn MaskAddressWith(Address: &str, Keep: usize) -> String {
+	let Chars: Vec<char> = Address.chars().collect();
+	if Chars.len() <= Keep * 2 { return Address.to_string(); }
+
+	let Start: String = Chars[..Keep].iter().collect();
+	let End: String = Chars[Chars.len() - Keep..].iter().collect();
+
+	format!("{Start}…{End}")
+}
+
+
+
+/// Encodes bytes in Monero's base58: each 8 byte block becomes 11 characters, and a shorter final block as few as
+/// will hold it, padded with leading `1`s. So unlike Bitcoin's base58 the output's length depends only on the input's.
+pub fn EncodeBase58(Input: &[u8]) -> String {
+	let mut Output = String::with_capacity(Input.len() / 8 * 11 + 11);
+
+	for Block in Input.chunks(8) {
+		let mut Number = Block.iter().fold(0u128, |N, B| N << 8 | *B as u128);
+
+		let mut Encoded = vec![ALPHABET[0]; ENCODED_BLOCK_SIZES[Block.len()]];
+		for C in Encoded.iter_mut().rev() {
+			*C = ALPHABET[(Number % 58) as usize];
+			Number /= 58;
+		}
+		Output.extend(Encoded.into_iter().map(char::from));
+	}
+
+	Output
+}
+
+/// Decodes Monero's base58, see [`EncodeBase58`]. Returns `None` for a character outside [`ALPHABET`], a final block
+/// of a length no block encodes to, or a block whose value overflows its size.
+pub fn DecodeBase58(Input: &str) -> Option<Vec<u8>> {
+	let mut Output = Vec::with_capacity(Input.len() * 8 / 11 + 1);
+
+	for Block in Input.as_bytes().chunks(11) {
+		let Size = ENCODED_BLOCK_SIZES.iter().position(|S| *S == Block.len())?;
+
+		let mut Number: u128 = 0;
+		for C in Block {
+			let Digit = ALPHABET.iter().position(|A| A == C)?;
+			Number = Number * 58 + Digit as u128;
+		}
+
+		// The decoded value must fit in the block's byte size
+		if Number >> (Size * 8) != 0 { return None; }
+		Output.extend_from_slice(&Number.to_be_bytes()[16 - Size..]);
+	}
+
+	Some(Output)
+}
+
+/// Decodes the unsigned LEB128 varint at the start of `Input`, e.g. an address's network prefix, returning its value
+/// and how many bytes it took up.
+pub fn DecodeVarint(Input: &[u8]) -> Option<(u64, usize)> {
+	let mut Value: u64 = 0;
+	for (I, B) in Input.iter().enumerate().take(10) {
+		Value |= ((B & 0x7f) as u64) << (I * 7);
+		if B & 0x80 == 0 { return Some((Value, I + 1)); }
+	}
+
+	None
+}
+
+/// Returns the checksum an address's bytes end with: the first [`CHECKSUM_LENGTH`] bytes of the [`Keccak256`] of the
+/// bytes before it.
+pub fn Checksum(Body: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+	let mut Output = [0u8; CHECKSUM_LENGTH];
+	Output.copy_from_slice(&Keccak256(Body)[..CHECKSUM_LENGTH]);
+
+	Output
+}
+
+/// Returns the Keccak-256 hash of `Input`, the original Keccak that Monero uses rather than the standardized SHA3-256.
+pub fn Keccak256(Input: &[u8]) -> [u8; 32] {
+	let mut Hasher = Keccak::v256();
+	Hasher.update(Input);
+	let mut Output = [0u8; 32];
+	Hasher.finalize(&mut Output);
+
+	Output
+}
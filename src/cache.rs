@@ -0,0 +1,60 @@
+//! A cache of decoded requests, for servers that decode the same codes over and over.
+use std::{num::NonZeroUsize, sync::Mutex};
+use lru::LruCache;
+use crate::{MoneroRequest, MoneroRequestError, RequestCodec, StandardCodec, MAX_ENCODED_LENGTH};
+
+
+
+/// Memoizes [`DecodePaymentRequest`](crate::DecodePaymentRequest), keyed by the encoded string.
+///
+/// The cache holds at most `Capacity` requests. Once full, adding a request evicts the one that was least recently
+/// decoded or looked up. Only successful decodes are cached, so a bad code is decoded (and rejected) every time. Codes
+/// longer than [`MAX_ENCODED_LENGTH`] are rejected before the cache is touched, so they can't be used to fill it with
+/// large keys.
+///
+/// The cache is behind a mutex, so a single `DecodeCache` can be shared between threads, e.g. in an `Arc`. The lock is
+/// held only while reading or updating the cache, not while decoding.
+#[derive(Debug)]
+pub struct DecodeCache {
+	Cache: Mutex<LruCache<String, MoneroRequest>>
+}
+
+impl DecodeCache {
+	/// Creates an empty cache holding up to `Capacity` requests.
+	pub fn new(Capacity: NonZeroUsize) -> Self {
+		DecodeCache { Cache: Mutex::new(LruCache::new(Capacity)) }
+	}
+
+	/// Returns the number of requests currently cached.
+	pub fn Len(&self) -> usize {
+		self.Lock().len()
+	}
+
+	/// Removes every cached request.
+	pub fn Clear(&self) {
+		self.Lock().clear();
+	}
+
+	// A panic while the lock was held can't leave the cache half updated, so a poisoned lock is still safe to use
+	fn Lock(&self) -> std::sync::MutexGuard<'_, LruCache<String, MoneroRequest>> {
+		self.Cache.lock().unwrap_or_else(|e| e.into_inner())
+	}
+}
+
+impl RequestCodec for DecodeCache {
+	/// Encodes with [`StandardCodec`]. Encoding isn't cached.
+	fn Encode(&self, Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+		StandardCodec::default().Encode(Request)
+	}
+
+	fn Decode(&self, Request: &str) -> Result<MoneroRequest, MoneroRequestError> {
+		if Request.len() > MAX_ENCODED_LENGTH { return Err(MoneroRequestError::InvalidInput("Encoded request is too long.")); }
+
+		if let Some(Cached) = self.Lock().get(Request) { return Ok(Cached.clone()); }
+
+		let Output = StandardCodec::default().Decode(Request)?;
+		self.Lock().put(Request.to_string(), Output.clone());
+
+		Ok(Output)
+	}
+}
@@ -0,0 +1,28 @@
+//! CBOR serialization of [`MoneroRequest`] for compact local storage. This is not part of the Monero Payment Request
+//! Standard; use [`EncodePaymentRequest`](crate::EncodePaymentRequest) for anything shared with other wallets.
+use crate::{MoneroRequest, MoneroRequestError};
+
+
+
+/// Validates the request and serializes it to CBOR.
+///
+/// Defaults are applied the same way as [`MoneroRequest::Normalized`], so the stored bytes always hold a fully
+/// resolved request.
+pub fn ToCbor(Request: MoneroRequest) -> Result<Vec<u8>, MoneroRequestError> {
+	let Request = Request.Normalized()?;
+
+	let mut Output = Vec::new();
+	if let Err(e) = ciborium::into_writer(&Request, &mut Output) { return Err(MoneroRequestError::CborSerializeError(e)); }
+
+	return Ok(Output);
+}
+
+/// Deserializes a [`MoneroRequest`] previously stored with [`ToCbor`].
+pub fn FromCbor(Input: &[u8]) -> Result<MoneroRequest, MoneroRequestError> {
+	let Request: MoneroRequest = match ciborium::from_reader(Input) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::CborDeserializeError(e))
+	};
+
+	return Ok(Request);
+}
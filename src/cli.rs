@@ -0,0 +1,30 @@
+//! [clap](https://docs.rs/clap) integration, so the crate's validated types can be used directly as typed arguments.
+//!
+//! The types implement `FromStr`, so `clap::value_parser!` already works with them. These parsers are the same thing
+//! spelled out, for builder-style commands:
+//!
+//! ```ignore
+//! Arg::new("wallet").value_parser(MoneroRequest_Rust::WalletAddressParser())
+//! ```
+//!
+//! Invalid values are reported with the validation message, e.g. `invalid value 'abc' for '<wallet>': Incorrect seller
+//! wallet address length.`
+use clap::builder::ValueParser;
+use crate::{Currency, PaymentID, WalletAddress};
+
+
+
+/// Parses and validates a [`WalletAddress`] argument.
+pub fn WalletAddressParser() -> ValueParser {
+	ValueParser::new(|Value: &str| WalletAddress::try_from(Value))
+}
+
+/// Parses and validates a [`PaymentID`] argument.
+pub fn PaymentIDParser() -> ValueParser {
+	ValueParser::new(|Value: &str| PaymentID::try_from(Value))
+}
+
+/// Parses a [`Currency`] argument from its code in any case, e.g. `XMR` or `xmr`.
+pub fn CurrencyParser() -> ValueParser {
+	ValueParser::new(|Value: &str| Currency::FromIsoCode(Value))
+}
@@ -0,0 +1,547 @@
+//! The wire format: turning a [`MoneroRequest`] into a `monero-request:` string and back.
+use std::{io::{BufRead, Read, Write}, sync::LazyLock};
+use base64::Engine;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use sha2::{Digest, Sha256};
+use crate::{MoneroRequest, MoneroRequestError, ValidateWalletAddress, ValidationOptions, LATEST_VERSION, SUPPORTED_VERSIONS};
+
+
+
+/// The longest encoded request that will be decoded. Larger than any QR code can hold, so genuine codes are never
+/// near it.
+pub const MAX_ENCODED_LENGTH: usize = 8192;
+
+/// The largest JSON payload a request body may decompress to. Gzip can compress repetitive input by over 1000x, so
+/// without this a short code could inflate to gigabytes.
+pub const MAX_PAYLOAD_BYTES: usize = 65536;
+
+
+/// A wire format for Monero Payment Requests.
+///
+/// [`StandardCodec`] implements the Monero Payment Request Standard and is what the free functions like
+/// [`EncodePaymentRequest`] and [`DecodePaymentRequest`] use. Other implementations can swap in a different format
+/// without changing call sites.
+pub trait RequestCodec {
+	/// Validates the request and encodes it.
+	fn Encode(&self, Request: &MoneroRequest) -> Result<String, MoneroRequestError>;
+
+	/// Decodes an encoded request.
+	fn Decode(&self, Request: &str) -> Result<MoneroRequest, MoneroRequestError>;
+}
+
+
+/// The Monero Payment Request Standard's format: the request as JSON, gzipped, base64'd, and prefixed with
+/// `monero-request:<version>:`.
+///
+/// Requests are validated with [`Options`](StandardCodec::Options) before they're encoded.
+#[derive(Debug, Clone, Default)]
+pub struct StandardCodec {
+	pub Options: ValidationOptions
+}
+
+impl RequestCodec for StandardCodec {
+	fn Encode(&self, Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+		// Validate input
+		let Request = Request.clone().NormalizedWith(&self.Options)?;
+
+		return EncodeInFormat(&JsonFormat, &Request);
+	}
+
+	fn Decode(&self, Request: &str) -> Result<MoneroRequest, MoneroRequestError> {
+		return DecodeInFormat(&JsonFormat, Request);
+	}
+}
+
+
+
+/// Accepts a valid Monero Payment Request and returns a [`MoneroRequest`] containing the decoded information.
+pub fn DecodePaymentRequest(Request: String) -> Result<MoneroRequest, MoneroRequestError> {
+	return StandardCodec::default().Decode(&Request);
+}
+
+
+/// Same as [`DecodePaymentRequest`], but also accepts the legacy un-versioned form `monero-request:<body>`.
+///
+/// The heuristic: if the input doesn't parse as a versioned code, and splits on `:` into exactly two parts where the
+/// first is `monero-request`, the second part is decoded as a version `1` body. Base64 never contains a `:`, so the two
+/// forms can't be confused. The decoded payload must still say version `1`.
+///
+/// This is opt-in as it makes a truncated or mangled versioned code harder to tell from a legacy one; prefer
+/// [`DecodePaymentRequest`] unless you have old codes to support.
+pub fn DecodeLenient(Request: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	if Request.len() > MAX_ENCODED_LENGTH { return Err(MoneroRequestError::InvalidInput("Encoded request is too long.")); }
+
+	let Body = match Request.split(':').collect::<Vec<&str>>()[..] {
+		["monero-request", Body] => Body,
+		_ => return StandardCodec::default().Decode(Request)
+	};
+
+	let mut Request = DecodePayload(Body)?;
+	ReconcileVersion(&mut Request, "1")?;
+
+	return Ok(Request);
+}
+
+
+/// Returns true if the string looks like a valid encoded request: it has a `monero-request:<version>:` prefix with a
+/// supported version, and its body is base64 that gunzips to a JSON object. The request itself isn't deserialized or
+/// validated, making this a cheap filter for untrusted input, e.g. in a webhook handler. Use [`DecodePaymentRequest`]
+/// when the contents are needed.
+pub fn IsValidEncoding(Request: &str) -> bool {
+	let Body = match SplitEnvelope(Request) {
+		Ok((_, r)) => r,
+		Err(_) => return false
+	};
+
+	let Json = match DecompressBody(Body) {
+		Ok(r) => r,
+		Err(_) => return false
+	};
+
+	return matches!(serde_json::from_str::<serde_json::Value>(&Json), Ok(serde_json::Value::Object(_)));
+}
+
+
+// The envelope patterns, compiled on first use and then shared across threads: a code within other text, a complete
+// code split into its parts, and just the header of a code of any version.
+static EMBEDDED_REQUEST: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"monero-request:\d:[A-Za-z0-9+/=]+").unwrap());
+static ENVELOPE: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"(?m)(?<header>monero-request):(?<version>\d):(?<request>.+)").unwrap());
+static HEADER: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^(?<scheme>monero-request):(?<version>\d+):.").unwrap());
+
+
+/// Finds and decodes every Monero Payment Request embedded in a block of text, such as an email or chat message.
+/// Codes that fail to decode are skipped.
+///
+/// A code starts at `monero-request:<version>:` and its body runs for as long as the characters are valid base64
+/// (`A-Z a-z 0-9 + / =`). So a body ends at whitespace, and also at punctuation such as a sentence's closing period.
+pub fn ExtractRequests(Text: &str) -> Vec<MoneroRequest> {
+	EMBEDDED_REQUEST
+		.find_iter(Text)
+		.filter_map(|M| DecodePaymentRequest(M.as_str().to_string()).ok())
+		.collect()
+}
+
+
+/// Lazily decodes a stream with one Monero Payment Request per line, such as a file opened with a `BufReader`. Blank
+/// lines are skipped and surrounding whitespace is trimmed. A line that fails to decode (or to read) yields an `Err`
+/// without ending the stream, so memory use stays bounded however long the input is.
+pub fn DecodeLines<R: BufRead>(Reader: R) -> impl Iterator<Item = Result<MoneroRequest, MoneroRequestError>> {
+	Reader.lines().filter_map(|Line| match Line {
+		Err(e) => Some(Err(MoneroRequestError::IoError(e))),
+		Ok(Line) if Line.trim().is_empty() => None,
+		Ok(Line) => Some(DecodePaymentRequest(Line.trim().to_string()))
+	})
+}
+
+
+// Splits a `monero-request:<version>:<body>` string into its version and body, checking the length, header and version.
+pub(crate) fn SplitEnvelope(Request: &str) -> Result<(&str, &str), MoneroRequestError> {
+	if Request.len() > MAX_ENCODED_LENGTH { return Err(MoneroRequestError::InvalidInput("Encoded request is too long.")); }
+
+	let Caps = match ENVELOPE.captures(Request) {
+		Some(r) => r,
+		None => { return Err(MoneroRequestError::InvalidInput("Invalid request string.")); }
+	};
+
+	if &Caps["header"] != "monero-request" { return Err(MoneroRequestError::InvalidInput("Invalid request header.")); }
+	if !SUPPORTED_VERSIONS.contains(&&Caps["version"]) { return Err(MoneroRequestError::InvalidInput("Invalid request version.")); }
+
+	// These unwraps are safe as the regex matched, so both groups are present
+	return Ok((Caps.name("version").unwrap().as_str(), Caps.name("request").unwrap().as_str()));
+}
+
+
+/// Parses only the `monero-request:<version>:` header of an encoded request, returning the scheme and version. The
+/// body isn't decoded, so this is cheap enough for logging or routing codes by version.
+///
+/// Unlike [`DecodePaymentRequest`] any version number is accepted, not just [`SUPPORTED_VERSIONS`], so codes from
+/// newer versions of the standard can be routed elsewhere.
+pub fn EnvelopeInfo(Request: &str) -> Result<(String, u32), MoneroRequestError> {
+	let Caps = match HEADER.captures(Request) {
+		Some(r) => r,
+		None => return Err(MoneroRequestError::InvalidInput("Invalid request header."))
+	};
+
+	let Version = match Caps["version"].parse::<u32>() {
+		Ok(r) => r,
+		Err(_) => return Err(MoneroRequestError::InvalidInput("Invalid request version."))
+	};
+
+	return Ok((Caps["scheme"].to_string(), Version));
+}
+
+
+/// Replaces the body of an encoded request with a short hash of the whole code, e.g.
+/// `monero-request:1:3f9c0a4e5d2b7a18`, for logging codes without the wallet and amount they carry. The same code
+/// always gives the same output, so log lines about one code can still be matched up.
+///
+/// The hash is the first 8 bytes of the code's SHA-256, in hex, the same hash as
+/// [`ContentHash`](MoneroRequest::ContentHash) uses. Input whose header doesn't parse, see [`EnvelopeInfo`], is hashed
+/// whole with no prefix.
+pub fn RedactEncoded(Request: &str) -> String {
+	let Hash: String = Sha256::digest(Request.as_bytes())[..8].iter().map(|B| format!("{B:02x}")).collect();
+
+	match EnvelopeInfo(Request) {
+		Ok((Scheme, Version)) => return format!("{}:{}:{}", Scheme, Version, Hash),
+		Err(_) => return Hash
+	}
+}
+
+
+/// Splits an encoded request into its scheme, version, and base64 body, without decompressing the body. For re-framing
+/// a code for a non-standard transport; [`FromParts`] puts it back together.
+///
+/// The scheme must be `monero-request` and the version one of [`SUPPORTED_VERSIONS`]. The body isn't checked, so a
+/// code that splits may still fail to decode.
+pub fn IntoParts(Request: &str) -> Result<(String, u32, String), MoneroRequestError> {
+	let (Version, Body) = SplitEnvelope(Request)?;
+
+	let Version = match Version.parse::<u32>() {
+		Ok(r) => r,
+		Err(_) => return Err(MoneroRequestError::InvalidInput("Invalid request version."))
+	};
+
+	return Ok(("monero-request".to_string(), Version, Body.to_string()));
+}
+
+
+/// Joins a scheme, version, and base64 body from [`IntoParts`] back into an encoded request. The scheme and version
+/// are checked as in [`IntoParts`].
+pub fn FromParts(Scheme: &str, Version: u32, Body: &str) -> Result<String, MoneroRequestError> {
+	if Scheme != "monero-request" { return Err(MoneroRequestError::InvalidInput("Invalid request header.")); }
+	if !SUPPORTED_VERSIONS.contains(&Version.to_string().as_str()) { return Err(MoneroRequestError::InvalidInput("Invalid request version.")); }
+	if Body.is_empty() { return Err(MoneroRequestError::InvalidInput("Invalid request string.")); }
+
+	return Ok(WrapEnvelope(&Version.to_string(), Body));
+}
+
+
+/// Decodes `Code` and returns every field that differs from `Expected`, so empty when they match. For checking codes
+/// from other implementations of the standard decode as they should. Only available with the `test-utils` feature.
+#[cfg(feature = "test-utils")]
+pub fn CheckReferenceVector(Code: &str, Expected: &MoneroRequest) -> Result<Vec<crate::FieldChange>, MoneroRequestError> {
+	return Ok(StandardCodec::default().Decode(Code)?.Diff(Expected));
+}
+
+
+/// Same as [`DecodePaymentRequest`], but also checks the decoded [`SellersWallet`](MoneroRequest::SellersWallet) with
+/// [`ValidateWalletAddress`], checksum included. Use this for codes from untrusted sources so that a corrupt address is
+/// caught before anything is paid to it.
+pub fn DecodePaymentRequestStrict(Request: String) -> Result<MoneroRequest, MoneroRequestError> {
+	let Request = DecodePaymentRequest(Request)?;
+	ValidateWalletAddress(&Request.SellersWallet)?;
+
+	return Ok(Request);
+}
+
+
+/// Decodes the base64 body of a Monero Payment Request, without the `monero-request:<version>:` prefix.
+///
+/// This skips the scheme and version framing entirely, so no envelope checks are made and
+/// [`Version`](MoneroRequest::Version) is left empty. Use [`DecodePaymentRequest`] for complete request strings. The
+/// body is held to [`MAX_ENCODED_LENGTH`] all the same.
+pub fn DecodePayload(Body: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	if Body.len() > MAX_ENCODED_LENGTH { return Err(MoneroRequestError::InvalidInput("Encoded request is too long.")); }
+
+	return DeserializePayload(&JsonFormat, Body);
+}
+
+
+// How a request is serialized inside the gzip and base64 envelope. The standard uses JSON, which is all the public
+// functions use, but the envelope doesn't care, so internal formats can reuse it.
+pub(crate) trait PayloadFormat {
+	fn Serialize(&self, Request: &MoneroRequest) -> Result<Vec<u8>, MoneroRequestError>;
+	fn Deserialize(&self, Payload: &[u8]) -> Result<MoneroRequest, MoneroRequestError>;
+}
+
+pub(crate) struct JsonFormat;
+
+// The request as its JSON payload. The version is carried in the `monero-request:<version>:` prefix, so it's left out; the
+// remaining keys are sorted, which is also the order the standard's reference tools write them in.
+pub(crate) fn PayloadJson(Request: &MoneroRequest) -> Result<serde_json::Value, MoneroRequestError> {
+	let mut Json = match serde_json::to_value(Request) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::SerdeError(e))
+	};
+
+	// This unwrap is safe as a struct always serializes to an object
+	Json.as_object_mut().unwrap().remove("version");
+
+	return Ok(Json);
+}
+
+impl PayloadFormat for JsonFormat {
+	fn Serialize(&self, Request: &MoneroRequest) -> Result<Vec<u8>, MoneroRequestError> {
+		match serde_json::to_vec(&PayloadJson(Request)?) {
+			Ok(r) => Ok(r),
+			Err(e) => Err(MoneroRequestError::SerdeError(e))
+		}
+	}
+
+	fn Deserialize(&self, Payload: &[u8]) -> Result<MoneroRequest, MoneroRequestError> {
+		// If the json is not properly formatted, or if fields are missing, this is going to have errors. Improper
+		// json is probably ok to fail on. No fields are marked optional in the spec, so this breaking for a missing
+		// field should also be ok.
+		match serde_json::from_slice(Payload) {
+			Ok(r) => Ok(r),
+			Err(e) => Err(MoneroRequestError::SerdeError(e))
+		}
+	}
+}
+
+// Serializes a request in the given format, then gzips and base64s it.
+pub(crate) fn SerializePayload<F: PayloadFormat>(Format: &F, Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	let Payload = Format.Serialize(Request)?;
+
+	return Ok(base64::engine::general_purpose::STANDARD.encode(GZip(&Payload)?));
+}
+
+// Un-base64s and un-gzips a body, then deserializes it in the given format.
+pub(crate) fn DeserializePayload<F: PayloadFormat>(Format: &F, Body: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	return Format.Deserialize(&DecompressBytes(Body)?);
+}
+
+// Encodes an already validated request in the given format, envelope and all.
+pub(crate) fn EncodeInFormat<F: PayloadFormat>(Format: &F, Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	return Ok(WrapEnvelope(&Request.Version, &SerializePayload(Format, Request)?));
+}
+
+// Decodes a complete code whose payload is in the given format, checking the envelope and the payload agree.
+pub(crate) fn DecodeInFormat<F: PayloadFormat>(Format: &F, Request: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	let (Version, Body) = SplitEnvelope(Request)?;
+
+	let mut Request = DeserializePayload(Format, Body)?;
+	ReconcileVersion(&mut Request, Version)?;
+
+	return Ok(Request);
+}
+
+// Prefixes a body with `monero-request:<version>:`.
+pub(crate) fn WrapEnvelope(Version: &str, Body: &str) -> String {
+	return format!("monero-request:{Version}:{Body}");
+}
+
+// Payloads normally leave the version to the envelope. If one does carry a version, the two must agree, otherwise
+// different parsers could interpret the same code differently depending on which one they trust.
+pub(crate) fn CheckPayloadVersion(Payload: &str, Envelope: &str) -> Result<(), MoneroRequestError> {
+	if !Payload.is_empty() && Payload != Envelope { return Err(MoneroRequestError::InvalidInput("Version mismatch between envelope and payload")); }
+
+	return Ok(());
+}
+
+// Checks a decoded request's version against the envelope's, see CheckPayloadVersion, and fills it in if it was left out.
+pub(crate) fn ReconcileVersion(Request: &mut MoneroRequest, Envelope: &str) -> Result<(), MoneroRequestError> {
+	CheckPayloadVersion(&Request.Version, Envelope)?;
+	if Request.Version.is_empty() { Request.Version = Envelope.to_string(); }
+
+	return Ok(());
+}
+
+
+// Un-base64s and un-gzips a request body back to its JSON.
+pub(crate) fn DecompressBody(Body: &str) -> Result<String, MoneroRequestError> {
+	let Request = match String::from_utf8(DecompressBytes(Body)?) {
+		Ok(r) => r,
+		Err(_) => return Err(MoneroRequestError::GZipError("Error decompressing data."))
+	};
+
+	return Ok(Request);
+}
+
+
+// Un-base64s and un-gzips a request body, whatever format the payload is in.
+fn DecompressBytes(Body: &str) -> Result<Vec<u8>, MoneroRequestError> {
+	return GUnzip(&UnBase64(Body)?);
+}
+
+// Un-Base64s a request body to GZip.
+pub(crate) fn UnBase64(Body: &str) -> Result<Vec<u8>, MoneroRequestError> {
+	match base64::engine::general_purpose::STANDARD.decode(Body) {
+		Ok(r) => Ok(r),
+		Err(e) => Err(MoneroRequestError::Base64Error(e))
+	}
+}
+
+// Un-GZips to the payload, reading one byte past the limit so that an oversized payload is caught without inflating all
+// of it.
+pub(crate) fn GUnzip(Compressed: &[u8]) -> Result<Vec<u8>, MoneroRequestError> {
+	let mut GZipOutput = Vec::new();
+	if GzDecoder::new(Compressed).take(MAX_PAYLOAD_BYTES as u64 + 1).read_to_end(&mut GZipOutput).is_err() {
+		return Err(MoneroRequestError::GZipError("Error decompressing data."));
+	}
+	if GZipOutput.len() > MAX_PAYLOAD_BYTES { return Err(MoneroRequestError::InvalidInput("Decompressed payload is too large.")); }
+
+	return Ok(GZipOutput);
+}
+
+
+/// Accepts a [`MoneroRequest`] struct, validates it, and outputs a String constituting a valid Monero Payment Request
+///
+/// If no [`PaymentID`](MoneroRequest::PaymentID) is provided, one will be generated randomly using [`GenRandomPaymentID`](crate::GenRandomPaymentID). If you explicitely
+/// do not want to have a PaymentID you may set it to [`NO_PAYMENT_ID`](crate::NO_PAYMENT_ID) (16 zeroes), or use
+/// [`PaymentIDMode::None`](crate::PaymentIDMode::None).
+///
+/// If no [`CustomLabel`](MoneroRequest::CustomLabel) is provided `Monero Payment Request` will be used.
+///
+/// [`Amount`](MoneroRequest::Amount) may be left empty to let the payer choose the amount. See [`MoneroRequest::IsOpenAmount`].
+///
+/// [`DaysPerBillingCycle`](MoneroRequest::DaysPerBillingCycle) may not be zero.
+///
+/// [`NumberOfPayments`](MoneroRequest::NumberOfPayments) may not be zero.
+///
+/// If no [`StartDate`](MoneroRequest::StartDate) is provided, now will be used. StartDate should be either in UTC time or contain enough
+/// information to be parsed into UTC time. The Chrono library is used for this parsing. To guarentee compatibility
+/// with this MoneroRequest library use Chrono's DateTime type.
+///
+/// If [`Version`](MoneroRequest::Version) is blank the latest version will be used.
+pub fn EncodePaymentRequest(Request: MoneroRequest) -> Result<String, MoneroRequestError> {
+	return StandardCodec::default().Encode(&Request);
+}
+
+
+/// Same as [`EncodePaymentRequest`], but returns the code as bytes for binary transports. The code is an ASCII prefix
+/// and base64, so the bytes are always valid UTF-8 (and ASCII).
+pub fn EncodeBytes(Request: &MoneroRequest) -> Result<Vec<u8>, MoneroRequestError> {
+	return Ok(StandardCodec::default().Encode(Request)?.into_bytes());
+}
+
+
+/// Same as [`DecodePaymentRequest`], but takes the code as bytes, e.g. from [`EncodeBytes`].
+pub fn DecodeBytes(Request: &[u8]) -> Result<MoneroRequest, MoneroRequestError> {
+	let Request = match std::str::from_utf8(Request) {
+		Ok(r) => r,
+		Err(_) => return Err(MoneroRequestError::InvalidInput("Encoded request is not valid UTF-8."))
+	};
+
+	return StandardCodec::default().Decode(Request);
+}
+
+
+/// Same as [`EncodePaymentRequest`], but validates with the given [`ValidationOptions`].
+pub fn EncodeWithOptions(Request: MoneroRequest, Options: &ValidationOptions) -> Result<String, MoneroRequestError> {
+	return StandardCodec { Options: Options.clone() }.Encode(&Request);
+}
+
+
+/// Same as [`EncodePaymentRequest`], but returns only the base64 body without the `monero-request:<version>:` prefix.
+///
+/// This skips the scheme and version framing entirely. It's meant for systems that store the body on its own and
+/// add the prefix themselves; the result is not a valid Monero Payment Request by itself. Decode it with
+/// [`DecodePayload`].
+pub fn EncodePayload(Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	let Request = Request.clone().Normalized()?;
+
+	return EncodeBody(&Request);
+}
+
+
+/// Validates the request and returns the JSON payload [`EncodePaymentRequest`] would compress, pretty-printed. For
+/// seeing what a code holds when another tool won't decode it; it's indented for reading, so isn't the exact bytes
+/// that get compressed.
+pub fn DebugJson(Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	let Request = Request.clone().Normalized()?;
+
+	let Json = match serde_json::to_string_pretty(&PayloadJson(&Request)?) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::SerdeError(e))
+	};
+
+	return Ok(Json);
+}
+
+
+// Serializes, compresses, and base64s an already validated request.
+fn EncodeBody(Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	return SerializePayload(&JsonFormat, Request);
+}
+
+
+fn GZip(Payload: &[u8]) -> Result<Vec<u8>, MoneroRequestError> {
+	let mut GZipOutput = GzEncoder::new(Vec::new(), Compression::best());
+	if GZipOutput.write_all(Payload).is_err() { return Err(MoneroRequestError::GZipError("Error compressing data."))}
+	let GZipOutput = match GZipOutput.finish() {
+		Ok(r) => r,
+		Err(_) => return Err(MoneroRequestError::GZipError("Error compressing data."))
+	};
+
+	return Ok(GZipOutput);
+}
+
+
+/// The size of a request at each step of encoding, as returned by [`MeasureSize`]. Useful for seeing which fields
+/// are worth shortening to fit a code in a smaller QR code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeReport {
+	/// The serialized JSON, in bytes.
+	pub JsonBytes: usize,
+	/// The gzipped JSON, in bytes.
+	pub GZipBytes: usize,
+	/// The base64 body, in characters.
+	pub Base64Chars: usize,
+	/// The complete code including the `monero-request:<version>:` prefix, in characters.
+	pub TotalWithPrefix: usize
+}
+
+/// Validates the request and reports its size at each step of [`EncodePaymentRequest`], see [`SizeReport`].
+/// Compression is most effective on repetitive text, so `GZipBytes` over `JsonBytes` shows how much it helped.
+pub fn MeasureSize(Request: &MoneroRequest) -> Result<SizeReport, MoneroRequestError> {
+	let Request = Request.clone().Normalized()?;
+
+	let Json = match serde_json::to_string(&PayloadJson(&Request)?) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::SerdeError(e))
+	};
+	let GZipBytes = GZip(Json.as_bytes())?.len();
+
+	// Base64 pads to whole 4 character groups of 3 bytes
+	let Base64Chars = GZipBytes.div_ceil(3) * 4;
+
+	return Ok(SizeReport {
+		JsonBytes: Json.len(),
+		GZipBytes,
+		Base64Chars,
+		TotalWithPrefix: WrapEnvelope(&Request.Version, "").len() + Base64Chars
+	});
+}
+
+
+/// Returns the length in characters of the code [`EncodePaymentRequest`] would produce, without building it. Useful for
+/// checking a request fits a QR code size, e.g. while trying out labels. Same as
+/// [`SizeReport`]'s `TotalWithPrefix`.
+///
+/// Fields that validation fills in, a random PaymentID and the current StartDate, change how well the payload
+/// compresses. Set them first if the length must match a later encode exactly.
+pub fn EncodedLen(Request: &MoneroRequest) -> Result<usize, MoneroRequestError> {
+	return Ok(MeasureSize(Request)?.TotalWithPrefix);
+}
+
+
+/// Same as [`EncodePaymentRequest`], but encodes using the given version of the standard regardless of what
+/// [`Version`](MoneroRequest::Version) is set to. The version is written only to the `monero-request:<version>:` prefix;
+/// the payload has no version field.
+///
+/// The version must be one of [`SUPPORTED_VERSIONS`].
+pub fn EncodeWithVersion(mut Request: MoneroRequest, Version: &str) -> Result<String, MoneroRequestError> {
+	if !SUPPORTED_VERSIONS.contains(&Version) { return Err(MoneroRequestError::InvalidInput("Unsupported version.")); }
+	Request.Version = Version.to_string();
+
+	return EncodePaymentRequest(Request);
+}
+
+
+/// Decodes a code made with any of the [`SUPPORTED_VERSIONS`] and re-encodes it as [`LATEST_VERSION`], so merchants can
+/// migrate old codes without re-entering them.
+///
+/// Each version's fields are mapped into the latest representation, and fields the older version lacks are filled with
+/// their defaults. Version 1 is currently the latest, so its codes come back with every field carried over as is and
+/// none defaulted.
+pub fn Upgrade(Encoded: &str) -> Result<String, MoneroRequestError> {
+	let Request = DecodePaymentRequest(Encoded.to_string())?;
+
+	let Request = match Request.Version.as_str() {
+		"1" => Request,
+		_ => return Err(MoneroRequestError::InvalidInput("Unsupported version."))
+	};
+
+	return EncodeWithVersion(Request, LATEST_VERSION);
+}
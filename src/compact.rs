@@ -0,0 +1,99 @@
+//! A compact key-value form of a request, for storing it in a URL shortener or redirect service.
+use std::collections::BTreeMap;
+use crate::{MoneroRequest, MoneroRequestError, DEFAULT_LABEL, LATEST_VERSION};
+
+
+
+impl MoneroRequest {
+	/// Returns the request as short key-value pairs, leaving out fields at their default, e.g. `{"a": "1", "c": "XMR",
+	/// "w": "4At3X5..."}`. Rebuild it with [`FromCompactParams`](MoneroRequest::FromCompactParams). This isn't the standard
+	/// wire format, just a lossless and smaller one for storage.
+	///
+	/// | Key | Field                                                          | Default left out        |
+	/// |-----|----------------------------------------------------------------|-------------------------|
+	/// | `l` | [`CustomLabel`](MoneroRequest::CustomLabel)                    | [`DEFAULT_LABEL`]       |
+	/// | `w` | [`SellersWallet`](MoneroRequest::SellersWallet)                | empty                   |
+	/// | `c` | [`Currency`](MoneroRequest::Currency)                          | empty                   |
+	/// | `a` | [`Amount`](MoneroRequest::Amount)                              | empty                   |
+	/// | `p` | [`PaymentID`](MoneroRequest::PaymentID)                        | empty                   |
+	/// | `s` | [`StartDate`](MoneroRequest::StartDate)                        | empty                   |
+	/// | `d` | [`DaysPerBillingCycle`](MoneroRequest::DaysPerBillingCycle)    | `30`                    |
+	/// | `n` | [`NumberOfPayments`](MoneroRequest::NumberOfPayments)          | `1`                     |
+	/// | `u` | [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL)      | empty                   |
+	/// | `v` | [`Version`](MoneroRequest::Version)                            | [`LATEST_VERSION`]      |
+	///
+	/// A field that differs from its default is always written, even when empty, so every request round trips exactly.
+	pub fn ToCompactParams(&self) -> BTreeMap<String, String> {
+		let Defaults = CompactValues(&CompactDefaults());
+
+		let Output = CompactValues(self).into_iter()
+			.filter(|(Key, Value)| *Value != Defaults[Key])
+			.map(|(Key, Value)| (Key.to_string(), Value))
+			.collect();
+
+		return Output;
+	}
+
+	/// Rebuilds a request from [`ToCompactParams`](MoneroRequest::ToCompactParams), filling missing keys with their
+	/// defaults. Unknown keys are an error, so that params from something else aren't mistaken for a request.
+	pub fn FromCompactParams(Params: &BTreeMap<String, String>) -> Result<MoneroRequest, MoneroRequestError> {
+		let mut Request = CompactDefaults();
+
+		for (Key, Value) in Params {
+			match Key.as_str() {
+				"l" => Request.CustomLabel = Value.clone(),
+				"w" => Request.SellersWallet = Value.clone(),
+				"c" => Request.Currency = Value.clone(),
+				"a" => Request.Amount = Value.clone(),
+				"p" => Request.PaymentID = Value.clone(),
+				"s" => Request.StartDate = Value.clone(),
+				"d" => Request.DaysPerBillingCycle = ParseCompactNumber(Value)?,
+				"n" => Request.NumberOfPayments = ParseCompactNumber(Value)?,
+				"u" => Request.ChangeIndicatorURL = Value.clone(),
+				"v" => Request.Version = Value.clone(),
+				_ => return Err(MoneroRequestError::InvalidInput("Unknown compact parameter."))
+			}
+		}
+
+		return Ok(Request);
+	}
+}
+
+// The values a missing key stands for.
+fn CompactDefaults() -> MoneroRequest {
+	MoneroRequest {
+		Amount: "".to_string(),
+		ChangeIndicatorURL: "".to_string(),
+		Currency: "".to_string(),
+		CustomLabel: DEFAULT_LABEL.to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 1,
+		PaymentID: "".to_string(),
+		SellersWallet: "".to_string(),
+		StartDate: "".to_string(),
+		Version: LATEST_VERSION.to_string()
+	}
+}
+
+// Every field under its compact key.
+fn CompactValues(Request: &MoneroRequest) -> BTreeMap<&'static str, String> {
+	BTreeMap::from([
+		("l", Request.CustomLabel.clone()),
+		("w", Request.SellersWallet.clone()),
+		("c", Request.Currency.clone()),
+		("a", Request.Amount.clone()),
+		("p", Request.PaymentID.clone()),
+		("s", Request.StartDate.clone()),
+		("d", Request.DaysPerBillingCycle.to_string()),
+		("n", Request.NumberOfPayments.to_string()),
+		("u", Request.ChangeIndicatorURL.clone()),
+		("v", Request.Version.clone())
+	])
+}
+
+fn ParseCompactNumber(Value: &str) -> Result<u8, MoneroRequestError> {
+	match Value.parse::<u8>() {
+		Ok(r) => Ok(r),
+		Err(_) => Err(MoneroRequestError::InvalidInput("Invalid compact parameter value."))
+	}
+}
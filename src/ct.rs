@@ -0,0 +1,29 @@
+//! Constant time comparison of [`MoneroRequest`]s, for payment matching backends.
+use subtle::ConstantTimeEq;
+use crate::MoneroRequest;
+
+
+
+impl MoneroRequest {
+	/// Returns true if both requests are identical, comparing [`SellersWallet`](MoneroRequest::SellersWallet) and
+	/// [`PaymentID`](MoneroRequest::PaymentID) in constant time so that matching incoming payments doesn't leak how much
+	/// of either matched. The remaining fields aren't sensitive and compare normally.
+	///
+	/// Only the lengths of the sensitive fields may leak, and those are fixed by the standard anyway.
+	pub fn CtEq(&self, Other: &MoneroRequest) -> bool {
+		// Both sensitive comparisons always run, regardless of the other's result
+		let Sensitive = self.SellersWallet.as_bytes().ct_eq(Other.SellersWallet.as_bytes())
+			& self.PaymentID.as_bytes().ct_eq(Other.PaymentID.as_bytes());
+
+		let Rest = self.CustomLabel == Other.CustomLabel
+			&& self.Currency == Other.Currency
+			&& self.Amount == Other.Amount
+			&& self.StartDate == Other.StartDate
+			&& self.DaysPerBillingCycle == Other.DaysPerBillingCycle
+			&& self.NumberOfPayments == Other.NumberOfPayments
+			&& self.ChangeIndicatorURL == Other.ChangeIndicatorURL
+			&& self.Version == Other.Version;
+
+		return bool::from(Sensitive) && Rest;
+	}
+}
@@ -0,0 +1,129 @@
+//! Layer by layer diagnosis of an encoded request, for working out why a code won't decode.
+use crate::{GUnzip, JsonFormat, Lint, MoneroRequest, OwnedError, PayloadFormat, ReconcileVersion, UnBase64, MAX_ENCODED_LENGTH, SUPPORTED_VERSIONS};
+
+
+
+/// The layers of an encoded request, outermost first: `monero-request:<version>:` then a base64 body of gzipped JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+	/// The `monero-request:` prefix and the length limit, see [`MAX_ENCODED_LENGTH`].
+	Prefix,
+	/// The version in the header, which must be one of [`SUPPORTED_VERSIONS`].
+	Version,
+	/// The base64 body.
+	Base64,
+	/// The gzip stream inside it.
+	GZip,
+	/// The JSON payload, including the version it carries if any.
+	Json,
+	/// Validation of the decoded fields, as [`MoneroRequest::Check`] does.
+	Fields
+}
+
+/// How one [`Layer`] of a [`DiagnosisReport`] fared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LayerStatus {
+	Passed,
+	/// The layer is malformed, for the given reason.
+	Failed(OwnedError),
+	/// An earlier layer failed, so this one couldn't be looked at.
+	NotReached
+}
+
+/// The result of [`Diagnose`]: every layer's status, plus whatever could be decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiagnosisReport {
+	/// Every [`Layer`] in order with its status. At most one has failed, and the layers after it weren't reached.
+	pub Layers: Vec<(Layer, LayerStatus)>,
+	/// The decoded request, if the JSON layer passed. Kept even when its fields fail validation, as they're usually
+	/// what's worth seeing.
+	pub Request: Option<MoneroRequest>,
+	/// The decoded request's [`Lint`]s, empty if it wasn't decoded.
+	pub Lints: Vec<Lint>
+}
+
+impl DiagnosisReport {
+	/// Returns the layer that failed and why, or `None` if the code is valid.
+	pub fn FirstFailure(&self) -> Option<(Layer, &OwnedError)> {
+		self.Layers.iter().find_map(|(Layer, Status)| match Status {
+			LayerStatus::Failed(e) => Some((*Layer, e)),
+			_ => None
+		})
+	}
+
+	/// Returns true if every layer passed. Lints don't count against this.
+	pub fn IsValid(&self) -> bool {
+		self.Layers.iter().all(|(_, Status)| *Status == LayerStatus::Passed)
+	}
+}
+
+
+
+/// Decodes `Request` one layer at a time and reports where it went wrong, for support tools looking at a bad code
+/// someone sent in. More detailed than [`IsValidEncoding`](crate::IsValidEncoding), and unlike
+/// [`DecodePaymentRequest`](crate::DecodePaymentRequest) the decoded request is kept when only its fields are invalid.
+///
+/// Stricter than decoding in one respect: the code must start with `monero-request:`, with nothing before it.
+pub fn Diagnose(Request: &str) -> DiagnosisReport {
+	let mut Report = DiagnosisReport { Layers: Vec::new(), Request: None, Lints: Vec::new() };
+
+	let Failure = Walk(Request, &mut Report);
+
+	// Mark the failed layer and every layer after it
+	if let Some((Failed, Error)) = Failure {
+		Report.Layers.push((Failed, LayerStatus::Failed(Error.ToOwnedError())));
+	}
+	let Remaining: Vec<Layer> = [Layer::Prefix, Layer::Version, Layer::Base64, Layer::GZip, Layer::Json, Layer::Fields].into_iter()
+		.skip(Report.Layers.len())
+		.collect();
+	Report.Layers.extend(Remaining.into_iter().map(|Layer| (Layer, LayerStatus::NotReached)));
+
+	return Report;
+}
+
+// Walks the layers in order, pushing each one that passes, and returns the first that fails with its error.
+fn Walk(Request: &str, Report: &mut DiagnosisReport) -> Option<(Layer, crate::MoneroRequestError)> {
+	use crate::MoneroRequestError::InvalidInput;
+
+	// Prefix
+	if Request.len() > MAX_ENCODED_LENGTH { return Some((Layer::Prefix, InvalidInput("Encoded request is too long."))); }
+	let (Version, Body) = match Request.strip_prefix("monero-request:").and_then(|r| r.split_once(':')) {
+		Some(r) => r,
+		None => return Some((Layer::Prefix, InvalidInput("Invalid request header.")))
+	};
+	Report.Layers.push((Layer::Prefix, LayerStatus::Passed));
+
+	// Version
+	if !SUPPORTED_VERSIONS.contains(&Version) { return Some((Layer::Version, InvalidInput("Invalid request version."))); }
+	Report.Layers.push((Layer::Version, LayerStatus::Passed));
+
+	// Base64
+	let Compressed = match UnBase64(Body) {
+		Ok(r) => r,
+		Err(e) => return Some((Layer::Base64, e))
+	};
+	Report.Layers.push((Layer::Base64, LayerStatus::Passed));
+
+	// GZip
+	let Payload = match GUnzip(&Compressed) {
+		Ok(r) => r,
+		Err(e) => return Some((Layer::GZip, e))
+	};
+	Report.Layers.push((Layer::GZip, LayerStatus::Passed));
+
+	// Json
+	let mut Decoded = match JsonFormat.Deserialize(&Payload) {
+		Ok(r) => r,
+		Err(e) => return Some((Layer::Json, e))
+	};
+	if let Err(e) = ReconcileVersion(&mut Decoded, Version) { return Some((Layer::Json, e)); }
+	Report.Lints = Decoded.Lint();
+	Report.Request = Some(Decoded.clone());
+	Report.Layers.push((Layer::Json, LayerStatus::Passed));
+
+	// Fields
+	if let Err(e) = Decoded.Check() { return Some((Layer::Fields, e)); }
+	Report.Layers.push((Layer::Fields, LayerStatus::Passed));
+
+	return None;
+}
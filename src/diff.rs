@@ -0,0 +1,42 @@
+//! Field-by-field comparison of two requests.
+use crate::{Field, MoneroRequest};
+
+
+
+/// A single field that differs between two requests, as returned by [`MoneroRequest::Diff`]. Values are rendered as
+/// strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+	pub Field: Field,
+	pub Old: String,
+	pub New: String
+}
+
+impl MoneroRequest {
+	/// Compares this request (the old one) against another (the new one) and returns every field that changed, in
+	/// declaration order. Fields are compared exactly as stored, so compare two [`Normalized`](MoneroRequest::Normalized)
+	/// requests to ignore differences that validation would erase.
+	pub fn Diff(&self, Other: &MoneroRequest) -> Vec<FieldChange> {
+		Field::ALL.iter()
+			.map(|F| (*F, self.FieldValue(*F), Other.FieldValue(*F)))
+			.filter(|(_, Old, New)| Old != New)
+			.map(|(Field, Old, New)| FieldChange { Field, Old, New })
+			.collect()
+	}
+
+	/// Returns the value of a field rendered as a string.
+	pub fn FieldValue(&self, Field: Field) -> String {
+		match Field {
+			Field::CustomLabel => self.CustomLabel.clone(),
+			Field::SellersWallet => self.SellersWallet.clone(),
+			Field::Currency => self.Currency.clone(),
+			Field::Amount => self.Amount.clone(),
+			Field::PaymentID => self.PaymentID.clone(),
+			Field::StartDate => self.StartDate.clone(),
+			Field::DaysPerBillingCycle => self.DaysPerBillingCycle.to_string(),
+			Field::NumberOfPayments => self.NumberOfPayments.to_string(),
+			Field::ChangeIndicatorURL => self.ChangeIndicatorURL.clone(),
+			Field::Version => self.Version.clone()
+		}
+	}
+}
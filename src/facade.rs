@@ -0,0 +1,58 @@
+//! [`PaymentRequest`], a single entry point for the common operations on a request.
+use chrono::{DateTime, Utc};
+use crate::{EncodePaymentRequest, MoneroRequest, MoneroRequestError, ToMoneroURI};
+
+
+
+/// A [`MoneroRequest`] with the common operations as methods, for discovering them by autocomplete and chaining them:
+///
+/// ```ignore
+/// let Code = PaymentRequest::from(Request).Validate()?.Encode()?;
+/// ```
+///
+/// Each method delegates to the existing function or method of the same purpose, which stay available for finer
+/// control, e.g. [`EncodeWithOptions`](crate::EncodeWithOptions).
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentRequest {
+	pub Request: MoneroRequest
+}
+
+impl PaymentRequest {
+	/// Validates the request and fills in its defaults, see [`MoneroRequest::Normalized`].
+	pub fn Validate(self) -> Result<PaymentRequest, MoneroRequestError> {
+		return Ok(PaymentRequest { Request: self.Request.Normalized()? });
+	}
+
+	/// Encodes the request, see [`EncodePaymentRequest`].
+	pub fn Encode(&self) -> Result<String, MoneroRequestError> {
+		return EncodePaymentRequest(self.Request.clone());
+	}
+
+	/// Encodes the request and renders it as a terminal QR code, see [`RenderQRCode`](crate::RenderQRCode).
+	#[cfg(feature = "qr")]
+	pub fn ToQR(&self) -> Result<String, MoneroRequestError> {
+		return crate::RenderQRCode(&self.Encode()?);
+	}
+
+	/// Builds a `monero:` URI for one-off payments, see [`ToMoneroURI`].
+	pub fn ToURI(&self) -> Result<String, MoneroRequestError> {
+		return ToMoneroURI(&self.Request);
+	}
+
+	/// Returns the due date of every payment, see [`MoneroRequest::PaymentSchedule`].
+	pub fn Schedule(&self) -> Result<Vec<DateTime<Utc>>, MoneroRequestError> {
+		return self.Request.PaymentSchedule();
+	}
+}
+
+impl From<MoneroRequest> for PaymentRequest {
+	fn from(Request: MoneroRequest) -> Self {
+		PaymentRequest { Request }
+	}
+}
+
+impl From<PaymentRequest> for MoneroRequest {
+	fn from(Request: PaymentRequest) -> Self {
+		Request.Request
+	}
+}
@@ -0,0 +1,36 @@
+//! A stable hash of a request's content, for deduplication and idempotency keys.
+use sha2::{Digest, Sha256};
+use crate::{MoneroRequest, MoneroRequestError, PayloadJson};
+
+
+
+impl MoneroRequest {
+	/// Returns the SHA-256 of the request's canonical form: its payload JSON after
+	/// [`Normalized`](MoneroRequest::Normalized), which has its keys in a fixed (sorted) order, prefixed with the
+	/// [`Version`](MoneroRequest::Version) and a `:`. So requests that differ only in what normalizing fills in or
+	/// rewrites, e.g. an empty label versus `Monero Payment Request`, a `usd` currency versus `USD`, or the same
+	/// StartDate in another time zone, hash the same. Use the hash as an idempotency key, e.g. to avoid storing the
+	/// same request twice.
+	///
+	/// Every field takes part, except that an empty [`PaymentID`](MoneroRequest::PaymentID) or
+	/// [`StartDate`](MoneroRequest::StartDate) is hashed as empty rather than as the random id or current time
+	/// normalizing would give it, so that hashing is repeatable. An integrated address's payment id isn't random, so
+	/// it's hashed as filled in. Errors if the request doesn't validate.
+	pub fn ContentHash(&self) -> Result<[u8; 32], MoneroRequestError> {
+		let mut Request = self.clone().Normalized()?;
+		if self.PaymentID.is_empty() && crate::IntegratedPaymentID(&self.SellersWallet)?.is_none() { Request.PaymentID.clear(); }
+		if self.StartDate.is_empty() { Request.StartDate.clear(); }
+
+		let Json = match serde_json::to_vec(&PayloadJson(&Request)?) {
+			Ok(r) => r,
+			Err(e) => return Err(MoneroRequestError::SerdeError(e))
+		};
+
+		let mut Hasher = Sha256::new();
+		Hasher.update(Request.Version.as_bytes());
+		Hasher.update(b":");
+		Hasher.update(&Json);
+
+		return Ok(Hasher.finalize().into());
+	}
+}
@@ -4,109 +4,694 @@ MoneroRequest_Rust is an easy way to decode/encode monero-request codes in Rust.
 
 Use [EncodePaymentRequest] and [DecodePaymentRequest] to create and decode requests. 
 [GenRandomPaymentID] is a helper function for generating random PaymentIDs valid to the 
-Monero protocol standard.
+Monero protocol standard. [ParseWalletAddress] verifies a wallet address checksum and reports its network and type.
 
 You may review the Monero Payment Request Standard [here](https://github.com/lukeprofits/Monero_Payment_Request_Standard).*/
 #![allow(non_snake_case)]
-use std::{io::Write, iter};
+#![allow(clippy::needless_return)]
+#![allow(clippy::len_zero)]
+use std::{borrow::Cow, iter, sync::LazyLock};
 use rand::prelude::*;
-use base64::Engine;
 use serde::{Deserialize, Serialize};
-use flate2::{write::GzDecoder, write::GzEncoder, Compression};
 use chrono::{prelude::*};
 
+pub mod address;
+pub use address::{AddressType, AddressValidator, IntegratedPaymentID, MaskAddress, MaskAddressWith, Network, ParseWalletAddress, ValidateWalletAddress};
+
+mod types;
+pub use types::{Currency, Field, PaymentID, SymbolPlacement, WalletAddress};
+
+#[cfg(feature = "codec")]
+mod codec;
+#[cfg(feature = "codec")]
+pub use codec::{DebugJson, DecodeBytes, DecodeLenient, DecodeLines, DecodePayload, DecodePaymentRequest, DecodePaymentRequestStrict, EncodeBytes, EncodedLen, EncodePayload, EncodePaymentRequest, EncodeWithOptions, EncodeWithVersion, EnvelopeInfo, ExtractRequests, FromParts, IntoParts, IsValidEncoding, MeasureSize, RedactEncoded, RequestCodec, SizeReport, StandardCodec, Upgrade, MAX_ENCODED_LENGTH, MAX_PAYLOAD_BYTES};
+#[cfg(all(feature = "codec", feature = "test-utils"))]
+pub use codec::CheckReferenceVector;
+#[cfg(feature = "codec")]
+pub(crate) use codec::{CheckPayloadVersion, DecompressBody, GUnzip, JsonFormat, PayloadFormat, PayloadJson, ReconcileVersion, SplitEnvelope, UnBase64};
+#[cfg(feature = "minified")]
+pub(crate) use codec::{DecodeInFormat, EncodeInFormat};
+#[cfg(feature = "deflate-raw")]
+pub(crate) use codec::WrapEnvelope;
 
+#[cfg(feature = "codec")]
+mod hash;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[cfg(feature = "codec")]
+mod diagnose;
+#[cfg(feature = "codec")]
+pub use diagnose::{Diagnose, DiagnosisReport, Layer, LayerStatus};
+
+#[cfg(feature = "codec")]
+mod facade;
+#[cfg(feature = "codec")]
+pub use facade::PaymentRequest;
+
+#[cfg(feature = "codec")]
+mod view;
+#[cfg(feature = "codec")]
+pub use view::{DecodeRef, MoneroRequestRef};
+
+mod compact;
+
+mod diff;
+pub use diff::FieldChange;
+
+mod lint;
+pub use lint::Lint;
+
+mod reconcile;
+pub use reconcile::ReceivedPayment;
+
+mod schedule;
+pub use schedule::RequestStatus;
+
+mod template;
+
+mod uri;
+pub use uri::{ParseMoneroURI, ToMoneroURI};
+
+#[cfg(feature = "clap")]
+mod cli;
+#[cfg(feature = "clap")]
+pub use cli::{CurrencyParser, PaymentIDParser, WalletAddressParser};
+
+#[cfg(feature = "cbor")]
+mod cbor;
+#[cfg(feature = "cbor")]
+pub use cbor::{FromCbor, ToCbor};
+
+#[cfg(feature = "zeroize")]
+mod ct;
+
+#[cfg(feature = "async")]
+mod stream;
+#[cfg(feature = "async")]
+pub use stream::DecodeStream;
+
+#[cfg(feature = "cache")]
+mod cache;
+#[cfg(feature = "cache")]
+pub use cache::DecodeCache;
+
+#[cfg(feature = "deflate-raw")]
+mod deflate;
+#[cfg(feature = "deflate-raw")]
+pub use deflate::{DecodeDeflateRaw, EncodeDeflateRaw};
+
+#[cfg(feature = "minified")]
+mod minified;
+#[cfg(feature = "minified")]
+pub use minified::{DecodeMinified, EncodeMinified};
+
+#[cfg(feature = "signing")]
+mod signing;
+#[cfg(feature = "signing")]
+pub use signing::{DecodeSigned, EncodeSigned};
+
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "schemars")]
+pub use schema::JsonSchema;
+
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "rayon")]
+pub use parallel::{EncodePar, EncodeParUnique};
+
+#[cfg(feature = "qr")]
+mod qr;
+#[cfg(feature = "qr")]
+pub use qr::RenderQRCode;
+
+/// The decimal type [`MoneroRequest::ApproxXMR`] takes and returns, re-exported so callers don't need their own
+/// dependency on `rust_decimal`.
+pub use rust_decimal::Decimal;
+
+
+
+/// Versions of the Monero Payment Request Standard this crate can encode and decode.
+pub const SUPPORTED_VERSIONS: &[&str] = &["1"];
+
+/// The version used when a request doesn't specify one.
+pub const LATEST_VERSION: &str = "1";
+
+/// The [`CustomLabel`](MoneroRequest::CustomLabel) used when a request doesn't specify one.
+pub const DEFAULT_LABEL: &str = "Monero Payment Request";
+
+/// The default for [`ValidationOptions::MaxXMRAmount`]. Total supply is around 18.4 million XMR plus tail emission, so
+/// this leaves plenty of headroom.
+pub const DEFAULT_MAX_XMR_AMOUNT: u64 = 100_000_000;
+
+/// The longest [`CustomLabel`](MoneroRequest::CustomLabel) accepted, in UTF-8 bytes rather than characters as bytes are
+/// what take up room in the code. An emoji is 4 bytes, so this is as few as 64 of them.
+pub const MAX_LABEL_BYTES: usize = 256;
+
+/// The longest [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL) accepted, in UTF-8 bytes.
+pub const MAX_URL_BYTES: usize = 512;
+
+/// The default for [`ValidationOptions::MaxNumberOfPayments`].
+pub const DEFAULT_MAX_NUMBER_OF_PAYMENTS: u32 = 1000;
+
+/// The longest prefix [`GenRandomPaymentIDWithPrefix`] accepts, in hex characters. Half of a PaymentID.
+pub const MAX_PAYMENT_ID_PREFIX_LEN: usize = 8;
+
+/// Optional validation rules used by [`MoneroRequest::NormalizedWith`] and [`EncodeWithOptions`]. The defaults are what
+/// [`EncodePaymentRequest`] uses.
+#[derive(Debug, Clone)]
+pub struct ValidationOptions {
+	/// Reject a [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL) whose host is an IP address rather than a
+	/// domain name, e.g. `https://127.0.0.1`. That's usually a leftover from testing, so turn this on for production
+	/// codes and leave it off when testing against a local server.
+	pub RequireDomainChangeIndicatorURL: bool,
+
+	/// The largest [`Amount`](MoneroRequest::Amount) accepted for XMR requests, in whole XMR. Nobody can pay more than
+	/// the total supply, so a larger amount almost always means piconeros were entered as XMR. Defaults to
+	/// [`DEFAULT_MAX_XMR_AMOUNT`].
+	pub MaxXMRAmount: u64,
+
+	/// What an empty [`PaymentID`](MoneroRequest::PaymentID) becomes, see [`PaymentIDMode`]. An integrated address's
+	/// payment id is used whatever the mode. Defaults to [`PaymentIDMode::Random`].
+	pub PaymentIDMode: PaymentIDMode,
+
+	/// Accept testnet and stagenet wallets in [`SellersWallet`](MoneroRequest::SellersWallet), for integration tests
+	/// against a test wallet. Their lengths and checksums are still checked, only the network and the leading `4`/`8`
+	/// requirement are relaxed. Regtest wallets use mainnet prefixes and are already accepted. Only available with the
+	/// `test-networks` feature, so production builds can't turn it on by accident. Defaults to off.
+	#[cfg(feature = "test-networks")]
+	pub AllowTestNetworks: bool,
+
+	/// Accept `http` as well as `https` for [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL), for testing
+	/// against a local server without TLS. Only available with the `test-networks` feature. Defaults to off.
+	#[cfg(feature = "test-networks")]
+	pub AllowHttpChangeIndicatorURL: bool,
+
+	/// Reject a [`StartDate`](MoneroRequest::StartDate) more than this many days from now. A date centuries away is
+	/// usually a bug, e.g. epoch seconds read as milliseconds, so something like `Some(3650)` (about 10 years) is a good
+	/// strict setting. Defaults to `None`, no limit.
+	pub MaxStartDateDaysAhead: Option<u32>,
+
+	/// Currencies whose subscriptions (a [`NumberOfPayments`](MoneroRequest::NumberOfPayments) other than 1) must set
+	/// an [`Amount`](MoneroRequest::Amount). A recurring fiat charge with no price is almost always a mistake, while an
+	/// open XMR subscription can be a recurring donation. Defaults to USD only, which allows:
+	///
+	/// | Currency | One-off, no amount | Subscription, no amount |
+	/// |----------|--------------------|-------------------------|
+	/// | XMR      | Yes                | Yes                     |
+	/// | USD      | Yes                | No                      |
+	pub AmountRequiredForSubscriptions: Vec<Currency>,
+
+	/// Round an [`Amount`](MoneroRequest::Amount) with more decimal places than its currency allows, rather than
+	/// rejecting it, e.g. `0.1234567890125` XMR becomes `0.123456789013`. Digits past a piconero can't be paid anyway.
+	/// Rounds half up, i.e. a dropped 5 or more rounds the last kept digit up. Defaults to off, so a mistyped amount is
+	/// caught rather than quietly changed.
+	pub RoundAmount: bool,
+
+	/// Checks [`SellersWallet`](MoneroRequest::SellersWallet) with this rather than the built-in checks, e.g. to accept
+	/// a new address format without forking. An integrated address's payment id is still used if the crate can parse
+	/// the address. Defaults to `None`, the built-in mainnet-only [`ValidateWalletAddress`] (relaxed by
+	/// `AllowTestNetworks`, which a custom validator ignores).
+	pub AddressValidator: Option<std::sync::Arc<dyn AddressValidator>>,
+
+	/// The largest [`NumberOfPayments`](MoneroRequest::NumberOfPayments) accepted, so that a schedule built from an
+	/// untrusted request stays a sensible size. Defaults to [`DEFAULT_MAX_NUMBER_OF_PAYMENTS`], which no `u8` count
+	/// reaches, so only a lower cap has an effect for now.
+	pub MaxNumberOfPayments: u32,
+
+	/// Further [`SellersWallet`](MoneroRequest::SellersWallet)s to reject, e.g. a platform's own hot wallet or addresses
+	/// reported as scams. Addresses with a null spend or view key, which nobody can spend from, are always rejected.
+	/// Defaults to empty.
+	pub DeniedWallets: Vec<String>,
+
+	/// The [`CustomLabel`](MoneroRequest::CustomLabel) used when a request doesn't specify one, e.g. a platform's own
+	/// `ACME Store Payment`. A label set on the request always wins over this. Defaults to [`DEFAULT_LABEL`].
+	pub DefaultLabel: String
+}
+
+impl Default for ValidationOptions {
+	fn default() -> Self {
+		ValidationOptions {
+			RequireDomainChangeIndicatorURL: false,
+			MaxXMRAmount: DEFAULT_MAX_XMR_AMOUNT,
+			PaymentIDMode: PaymentIDMode::Random,
+			#[cfg(feature = "test-networks")]
+			AllowTestNetworks: false,
+			#[cfg(feature = "test-networks")]
+			AllowHttpChangeIndicatorURL: false,
+			MaxStartDateDaysAhead: None,
+			AmountRequiredForSubscriptions: vec![Currency::USD],
+			RoundAmount: false,
+			AddressValidator: None,
+			MaxNumberOfPayments: DEFAULT_MAX_NUMBER_OF_PAYMENTS,
+			DeniedWallets: Vec::new(),
+			DefaultLabel: DEFAULT_LABEL.to_string()
+		}
+	}
+}
+
+/// The PaymentID that means a request has none, 16 zeroes. The standard requires the field, so this stands in for
+/// leaving it out.
+pub const NO_PAYMENT_ID: &str = "0000000000000000";
+
+/// How validation fills in an empty [`PaymentID`](MoneroRequest::PaymentID), set with
+/// [`ValidationOptions::PaymentIDMode`]. A PaymentID set on the request is always kept.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaymentIDMode {
+	/// Generate one with [`GenRandomPaymentID`], so each request's payments can be told apart.
+	Random,
+	/// Use [`NO_PAYMENT_ID`], for merchants who track payments by address alone.
+	None,
+	/// Use the given id, e.g. an invoice number the merchant already tracks. It's validated like any other PaymentID.
+	Explicit(String)
+}
+
+/// A Monero Payment Request, as encoded by [`EncodePaymentRequest`] and decoded by [`DecodePaymentRequest`].
+///
+/// The Monero Payment Request Standard marks every field as required, so none are `Option`s. A field not in use, such
+/// as a [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL) on a request that won't change, is an empty string,
+/// and a payload that leaves a field out fails to decode.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "zeroize", derive(zeroize::Zeroize))]	// So backends can wipe wallets and payment ids when done
+// Fields are declared in the order the reference implementation serializes them (sorted by key), so that encoded
+// output is byte for byte the same as the reference tools'.
 pub struct MoneroRequest {
-	pub CustomLabel: String,
-	pub SellersWallet: String,
-	pub Currency: String,
+	#[serde(rename = "amount", deserialize_with = "DeserializeAmount")]
 	pub Amount: String,
-	pub PaymentID: String,
-	pub StartDate: String,
+	#[serde(rename = "change_indicator_url")]
+	pub ChangeIndicatorURL: String,
+	#[serde(rename = "currency")]
+	pub Currency: String,
+	#[serde(rename = "custom_label")]
+	pub CustomLabel: String,
+	#[serde(rename = "days_per_billing_cycle")]
 	pub DaysPerBillingCycle: u8,
+	#[serde(rename = "number_of_payments")]
 	pub NumberOfPayments: u8,
-	pub ChangeIndicatorURL: String,
+	#[serde(rename = "payment_id")]
+	pub PaymentID: String,
+	#[serde(rename = "sellers_wallet")]
+	pub SellersWallet: String,
+	#[serde(rename = "start_date")]
+	pub StartDate: String,
+	/// The version is carried in the `monero-request:<version>:` prefix rather than the payload, and is filled in from
+	/// there when decoding. Other serializations, like CBOR, keep it.
+	#[serde(rename = "version", default)]
 	pub Version: String
 }
 
 impl MoneroRequest {
+	/// Validates the request and returns it with all defaults applied, exactly as [`EncodePaymentRequest`] would
+	/// serialize it. Useful for persisting the fully-resolved request without encoding it.
+	///
+	/// The following fields are filled or rewritten:
+	/// - [`CustomLabel`](MoneroRequest::CustomLabel) is set to `Monero Payment Request` if empty.
+	/// - [`PaymentID`](MoneroRequest::PaymentID) is lowercased, its canonical form, so that it can be matched against
+	///   wallet output (which is lowercase) with a plain comparison. If empty it's taken from the
+	///   [`SellersWallet`](MoneroRequest::SellersWallet) when that's an integrated address, otherwise generated with
+	///   [`GenRandomPaymentID`]. A PaymentID that differs from an integrated address's is an error.
+	/// - [`StartDate`](MoneroRequest::StartDate) is set to now if empty, otherwise re-parsed and rewritten in UTC, in
+	///   the form `2023-10-26T05:38:19.634Z`.
+	/// - [`Currency`](MoneroRequest::Currency) is uppercased, so `usd` is accepted and stored as `USD`.
+	/// - [`Version`](MoneroRequest::Version) is set to the latest version if empty.
+	///
+	/// Empty required fields fail with [`MoneroRequestError::MissingFields`], listing every one of them, so a mostly
+	/// blank request reports all it lacks at once.
+	///
+	/// Normalizing is idempotent: a normalized request normalizes to itself, so it's safe to call repeatedly.
+	pub fn Normalized(mut self) -> Result<MoneroRequest, MoneroRequestError> {
+		self.Validate()?;
+
+		return Ok(self);
+	}
+
+	/// Same as [`Normalized`](MoneroRequest::Normalized), but validates with the given [`ValidationOptions`].
+	pub fn NormalizedWith(mut self, Options: &ValidationOptions) -> Result<MoneroRequest, MoneroRequestError> {
+		self.ValidateWith(Options)?;
+
+		return Ok(self);
+	}
+
+	/// Validates the request without changing it, for read-only checks such as in a form handler that only has a
+	/// reference.
+	///
+	/// Fields that [`Normalized`](MoneroRequest::Normalized) would fill in, like an empty PaymentID or StartDate, are
+	/// accepted here, and fields it would reject are rejected with the same error. So `Check` succeeds exactly when
+	/// `Normalized` would, but leaves the request as it was; use `Normalized` for the filled-in request.
+	pub fn Check(&self) -> Result<(), MoneroRequestError> {
+		return self.CheckWith(&ValidationOptions::default());
+	}
+
+	/// Same as [`Check`](MoneroRequest::Check), but validates with the given [`ValidationOptions`].
+	pub fn CheckWith(&self, Options: &ValidationOptions) -> Result<(), MoneroRequestError> {
+		// Validation fills defaults in as it goes, so it runs on a scratch copy
+		return self.clone().ValidateWith(Options);
+	}
+
+	/// Returns the request with a fresh [`PaymentID`](MoneroRequest::PaymentID) from [`GenRandomPaymentID`], e.g. to make
+	/// a per-invoice copy of a template request without reusing its id.
+	///
+	/// An integrated address has its own payment id, which a fresh one would conflict with, so re-key those requests by
+	/// changing the address instead.
+	pub fn WithNewPaymentID(mut self) -> MoneroRequest {
+		self.PaymentID = GenRandomPaymentID();
+
+		return self;
+	}
+
+	/// Same as [`WithNewPaymentID`](MoneroRequest::WithNewPaymentID), but the id is generated from `Seed`, so the same
+	/// seed always gives the same id. For reproducible tests only, as seeded ids are predictable.
+	pub fn WithNewPaymentIDSeeded(mut self, Seed: u64) -> MoneroRequest {
+		self.PaymentID = GenRandomHexWith(&mut rand::rngs::StdRng::seed_from_u64(Seed), PAYMENT_ID_BYTES * 2, HexCase::Lower);
+
+		return self;
+	}
+
+	/// Builds a validated request paying the integrated address `Address`, with its embedded payment id as the
+	/// [`PaymentID`](MoneroRequest::PaymentID) and every other field at its default, as with
+	/// [`Normalized`](MoneroRequest::Normalized). An empty `Amount` makes an open amount request.
+	///
+	/// The address must be a valid mainnet integrated address; a standard address or subaddress is an error, since it
+	/// has no payment id to take.
+	pub fn FromIntegratedAddress(Address: &str, Currency: Currency, Amount: &str) -> Result<MoneroRequest, MoneroRequestError> {
+		ValidateWalletAddress(Address)?;
+		let PaymentID = match IntegratedPaymentID(Address)? {
+			Some(r) => r,
+			None => return Err(MoneroRequestError::InvalidInput("Not an integrated address."))
+		};
+
+		let Request = MoneroRequest {
+			Amount: Amount.to_string(),
+			ChangeIndicatorURL: "".to_string(),
+			Currency: Currency.IsoCode().to_string(),
+			CustomLabel: "".to_string(),
+			DaysPerBillingCycle: 30,
+			NumberOfPayments: 1,
+			PaymentID,
+			SellersWallet: Address.to_string(),
+			StartDate: "".to_string(),
+			Version: "".to_string()
+		};
+
+		return Request.Normalized();
+	}
+
+	/// Returns true if no [`Amount`](MoneroRequest::Amount) is set, meaning the payer chooses how much to send, as with
+	/// a donation. Such requests are valid and encode the amount as an empty string.
+	pub fn IsOpenAmount(&self) -> bool {
+		self.Amount.is_empty()
+	}
+
+	/// Returns the [`Network`] the [`SellersWallet`](MoneroRequest::SellersWallet) belongs to. Useful as a deploy-time
+	/// check that a request isn't still pointing at a stagenet or testnet wallet.
+	pub fn RequiresNetwork(&self) -> Result<Network, MoneroRequestError> {
+		let (Network, _) = ParseWalletAddress(&self.SellersWallet)?;
+
+		return Ok(Network);
+	}
+
+	/// Returns true if the [`SellersWallet`](MoneroRequest::SellersWallet) is an integrated address. Those carry their
+	/// own payment id, so wallet UIs can hide the separate payment id input.
+	pub fn UsesIntegratedAddress(&self) -> Result<bool, MoneroRequestError> {
+		let (_, Type) = ParseWalletAddress(&self.SellersWallet)?;
+
+		return Ok(Type == AddressType::Integrated);
+	}
+
+	/// Formats the [`Amount`](MoneroRequest::Amount) for display, e.g. `$1,234.45` for USD or `1,234.45 XMR` for XMR. The
+	/// whole part is grouped in thousands, the fractional part is kept as entered. Symbols and their placement come
+	/// from [`Currency::Symbol`] and [`Currency::SymbolPlacement`].
+	///
+	/// Open amounts display as an empty string, and amounts in unsupported currencies are followed by the currency code.
+	/// An amount that isn't a plain decimal, which validation would reject, is shown as entered rather than grouped.
+	pub fn DisplayAmount(&self) -> String {
+		if self.IsOpenAmount() { return String::new(); }
+
+		let Grouped = match SplitAmount(&self.Amount) {
+			Ok((Whole, Fraction)) => {
+				// The digits are all ASCII, so grouping by bytes is grouping by characters
+				let Groups: Vec<&str> = Whole.as_bytes().rchunks(3).rev().map(|G| std::str::from_utf8(G).unwrap()).collect();
+				if self.Amount.contains('.') { format!("{}.{Fraction}", Groups.join(",")) } else { Groups.join(",") }
+			},
+			Err(_) => self.Amount.clone()
+		};
+
+		let Output = match Currency::FromIsoCode(&self.Currency) {
+			Ok(C) => match C.SymbolPlacement() {
+				SymbolPlacement::Prefix => format!("{}{Grouped}", C.Symbol()),
+				SymbolPlacement::Suffix => format!("{Grouped} {}", C.Symbol())
+			},
+			Err(_) => format!("{Grouped} {}", self.Currency)
+		};
+
+		return Output;
+	}
+
+	/// Describes the request in one line for support tooling, e.g. `Monero Payment Request: $123.45/month for 12 months
+	/// to 4At3X5…hCeX2S, starting 2023-04-26.`, built from [`DisplayAmount`](MoneroRequest::DisplayAmount) and
+	/// [`MaskAddress`].
+	///
+	/// Billing cycles of 1, 7 and 30 days are named as a day, week and month. One-off requests leave the terms
+	/// out, and the start is left out if [`StartDate`](MoneroRequest::StartDate) doesn't parse.
+	pub fn Summary(&self) -> String {
+		let Label = if self.CustomLabel.is_empty() { DEFAULT_LABEL } else { &self.CustomLabel };
+		let Amount = if self.IsOpenAmount() { "Any amount".to_string() } else { self.DisplayAmount() };
+
+		let Cycle = match self.DaysPerBillingCycle {
+			1 => Some("day"),
+			7 => Some("week"),
+			30 => Some("month"),
+			_ => None
+		};
+		let Terms = match (self.NumberOfPayments, Cycle) {
+			(1, _) => String::new(),
+			(0, Some(C)) => format!("/{C}, ongoing"),
+			(0, None) => format!(" every {} days, ongoing", self.DaysPerBillingCycle),
+			(N, Some(C)) => format!("/{C} for {N} {C}s"),
+			(N, None) => format!(" every {} days for {N} payments", self.DaysPerBillingCycle)
+		};
+
+		let Start = match self.StartDate.parse::<DateTime<Utc>>() {
+			Ok(r) => format!(", starting {}", r.format("%Y-%m-%d")),
+			Err(_) => String::new()
+		};
+
+		return format!("{Label}: {Amount}{Terms} to {}{Start}.", MaskAddress(&self.SellersWallet));
+	}
+
+	/// Returns the [`Amount`](MoneroRequest::Amount) in piconeros, XMR's atomic unit (10^-12 XMR). Only defined for XMR
+	/// requests with an amount set.
+	///
+	/// Errors on more than 12 decimal places, and if the amount doesn't fit in a u64 (about 18.4 million XMR).
+	pub fn AmountAtomic(&self) -> Result<u64, MoneroRequestError> {
+		if self.Currency != "XMR" { return Err(MoneroRequestError::InvalidInput("Atomic amounts are only defined for XMR.")); }
+		if self.IsOpenAmount() { return Err(MoneroRequestError::InvalidInput("No amount set.")); }
+
+		let (Whole, Fraction) = SplitAmount(&self.Amount)?;
+		if Fraction.len() > Currency::XMR.DecimalPlaces() { return Err(MoneroRequestError::InvalidInput("Too many decimal places for XMR")); }
+
+		// Pad the fraction out to 12 digits so the whole thing parses as piconeros
+		let Digits = format!("{Whole}{Fraction:0<12}");
+		let Output = match Digits.trim_start_matches('0') {
+			"" => 0,
+			Digits => match Digits.parse::<u64>() {
+				Ok(r) => r,
+				Err(_) => return Err(MoneroRequestError::InvalidInput("XMR amount overflows atomic units."))
+			}
+		};
+
+		return Ok(Output);
+	}
+
+	/// Returns roughly how much XMR a USD request costs at `USDPerXMR` dollars per XMR, rounded half up to 12 decimal
+	/// places (piconeros), e.g. `0.166666666667` for $25 at $150. For showing the XMR cost next to the price.
+	///
+	/// Errors for requests in other currencies, open amounts, rates that aren't positive, and results too large for a
+	/// [`Decimal`].
+	pub fn ApproxXMR(&self, USDPerXMR: Decimal) -> Result<Decimal, MoneroRequestError> {
+		if self.Currency != "USD" { return Err(MoneroRequestError::InvalidInput("Approximate XMR is only defined for USD.")); }
+		if self.IsOpenAmount() { return Err(MoneroRequestError::InvalidInput("No amount set.")); }
+		if USDPerXMR <= Decimal::ZERO { return Err(MoneroRequestError::InvalidInput("Invalid exchange rate.")); }
+
+		let (Whole, Fraction) = SplitAmount(&self.Amount)?;
+		let Output = match format!("{Whole}.{Fraction}").parse::<Decimal>() {
+			Ok(Amount) => Amount.checked_div(USDPerXMR),
+			Err(_) => None
+		};
+
+		return match Output {
+			Some(r) => Ok(r.round_dp_with_strategy(Currency::XMR.DecimalPlaces() as u32, rust_decimal::RoundingStrategy::MidpointAwayFromZero)),
+			None => Err(MoneroRequestError::InvalidInput("Approximate XMR is too large to represent."))
+		};
+	}
+
 	fn Validate(&mut self) -> Result<(), MoneroRequestError> {
+		return self.ValidateWith(&ValidationOptions::default());
+	}
+
+	fn ValidateWith(&mut self, Options: &ValidationOptions) -> Result<(), MoneroRequestError> {
 		// Label
-		if self.CustomLabel.len() == 0 { self.CustomLabel = "Monero Payment Request".to_string() }
+		if self.CustomLabel.len() == 0 { self.CustomLabel = Options.DefaultLabel.clone() }
+		if self.CustomLabel.len() > MAX_LABEL_BYTES { return Err(MoneroRequestError::InvalidInput("CustomLabel exceeds 256 bytes")); }
+
+		// Required fields
+		// A mostly empty request, as on a first try, would otherwise fail on one field at a time, so every required field
+		// that's empty is listed at once. An empty amount makes an open request, so the amount is only required for a
+		// subscription in one of Options.AmountRequiredForSubscriptions, or with no currency to tell.
+		let AmountRequired = self.NumberOfPayments != 1 && match Currency::FromIsoCode(&self.Currency) {
+			Ok(C) => Options.AmountRequiredForSubscriptions.contains(&C),
+			Err(_) => self.Currency.is_empty()
+		};
+		let Missing: Vec<Field> = [
+			(Field::SellersWallet, self.SellersWallet.is_empty()),
+			(Field::Currency, self.Currency.is_empty()),
+			(Field::Amount, self.IsOpenAmount() && AmountRequired)
+		].into_iter().filter(|(_, Missing)| *Missing).map(|(Field, _)| Field).collect();
+		if !Missing.is_empty() { return Err(MoneroRequestError::MissingFields(Missing)); }
 
 		// Seller wallet address
-		match self.SellersWallet.len() {
-			0 => return Err(MoneroRequestError::InvalidInput("No seller wallet specified.")),
-			95 | 106 => {},
-			_ => return Err(MoneroRequestError::InvalidInput("Incorrect seller wallet address length."))
+		#[cfg(feature = "test-networks")]
+		let AllowTestNetworks = Options.AllowTestNetworks;
+		#[cfg(not(feature = "test-networks"))]
+		let AllowTestNetworks = false;
+		match Options.AddressValidator {
+			Some(ref r) => r.Validate(&self.SellersWallet)?,
+			None => address::ValidateWalletAddressOn(&self.SellersWallet, AllowTestNetworks)?
 		}
-
-		let FirstChar = self.SellersWallet.chars().next().unwrap();	// This unwrap should be safe as we checked lenth > 0 earlier
-		if FirstChar != '4' && FirstChar != '8' {
-			return Err(MoneroRequestError::InvalidInput("Invalid wallet address. Doesnt start with 4 or 8."));
-		}
-
-		for C in self.SellersWallet.chars() {
-			match C {
-				'0'..='9' | 'A'..='Z' | 'a'..='z' => {},
-				_ => return Err(MoneroRequestError::InvalidInput("Invalid character in wallet address."))
-			}
+		// A checksum only catches typos, not a well formed address that can't receive funds
+		if address::IsBurnAddress(&self.SellersWallet) || Options.DeniedWallets.contains(&self.SellersWallet) {
+			return Err(MoneroRequestError::InvalidInput("Seller wallet is a known invalid/burn address"));
 		}
 
 		// PaymentID
-		match self.PaymentID.len() {
-			0 => self.PaymentID = GenRandomPaymentID(),
-			1..=15 | 17.. => return Err(MoneroRequestError::InvalidInput("Invalid PaymentID length.")),
-			16 => {}
+		// An integrated address carries its own payment id, which takes precedence: an empty PaymentID is filled from
+		// the address rather than generated, and a different one is a contradiction.
+		// An address that passed a custom validator may be in a format the crate can't parse, which has no embedded id.
+		let Embedded = IntegratedPaymentID(&self.SellersWallet).unwrap_or(None);
+		if self.PaymentID.is_empty() {
+			self.PaymentID = match Embedded {
+				Some(ref r) => r.clone(),
+				None => match Options.PaymentIDMode {
+					PaymentIDMode::Random => GenRandomPaymentID(),
+					PaymentIDMode::None => NO_PAYMENT_ID.to_string(),
+					PaymentIDMode::Explicit(ref r) => r.clone()
+				}
+			};
 		}
-
-		for C in self.PaymentID.chars() {
-			match C {
-				'0'..='9' | 'a'..='f' => {}
-				_ => return Err(MoneroRequestError::InvalidInput("Invalid character in PaymentID."))
-			}
+		ValidatePaymentID(&self.PaymentID)?;
+		// Either case is accepted, but lowercase is what gets stored, whatever mix the input had
+		self.PaymentID.make_ascii_lowercase();
+		if Embedded.is_some_and(|E| E != self.PaymentID) {
+			return Err(MoneroRequestError::InvalidInput("PaymentID conflicts with the integrated address's payment id."));
 		}
 
 		// StartDate
+		// Written as RFC 3339 with milliseconds, e.g. `2023-10-26T05:38:19.634Z`, to match the reference implementation.
+		// That form parses back to the same instant, so re-validating leaves it untouched. Anything finer than a
+		// millisecond is dropped on the first pass only.
 		if self.StartDate.len() == 0 {
-			self.StartDate = Utc::now().to_string();
+			self.StartDate = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
 		} else {
-			self.StartDate = match self.StartDate.parse::<DateTime<Utc>>() {
-				Ok(r) => r.to_string(),
+			let Start = match self.StartDate.parse::<DateTime<Utc>>() {
+				Ok(r) => r,
 				Err(e) => return Err(MoneroRequestError::ChronoError(e))
+			};
+
+			if let Some(Days) = Options.MaxStartDateDaysAhead {
+				// A limit past the last representable date is no limit
+				if Utc::now().checked_add_signed(chrono::Duration::days(Days as i64)).is_some_and(|Max| Start > Max) {
+					return Err(MoneroRequestError::InvalidInput("StartDate too far in the future"));
+				}
 			}
+
+			self.StartDate = Start.to_rfc3339_opts(SecondsFormat::Millis, true);
 		}
 
 		// Currency
-		if !vec!["USD", "XMR"].contains(&self.Currency.as_str()) { return Err(MoneroRequestError::InvalidInput("Invalid Currency.")); }
+		// Matched in any case, and stored in the standard's uppercase
+		let Currency = match Currency::FromIsoCode(&self.Currency) {
+			Ok(r) => r,
+			Err(_) => return Err(MoneroRequestError::InvalidInput("Invalid Currency."))
+		};
+		self.Currency = Currency.IsoCode().to_string();
 
 		// Amount
-		if !regex::Regex::new(r"(?m)[\d,.]+").unwrap().is_match(&self.Amount) {
+		// Amounts follow the US convention: a period is the decimal point and commas group the whole part in threes. So
+		// `1,234` is 1234 and `1.234` is one and a bit, while forms that only make sense under another convention, like
+		// `1,23` or `1.234,56`, are rejected rather than guessed at.
+		if self.Amount.matches('.').count() > 1 || (self.Amount.contains(',') && !GROUPED_AMOUNT.is_match(&self.Amount)) {
+			return Err(MoneroRequestError::InvalidInput("Ambiguous amount format"));
+		}
+
+		// Otherwise only plain ASCII decimals are accepted: no sign, exponent, unit or non-ASCII digits. An empty amount is
+		// an open request where the payer decides, e.g. a donation.
+		if !self.IsOpenAmount() && !PLAIN_AMOUNT.is_match(&self.Amount) {
 			return Err(MoneroRequestError::InvalidInput("Invalid Amount."));
 		};
 
-		// Days per billing cycle
-		if self.DaysPerBillingCycle == 0 { return Err(MoneroRequestError::InvalidInput("DaysPerBillingCycle cannot be zero.")) }
+		if let Some((Whole, Fraction)) = self.Amount.split_once('.') {
+			if Fraction.len() > Currency.DecimalPlaces() && Options.RoundAmount {
+				self.Amount = RoundAmountHalfUp(Whole, Fraction, Currency.DecimalPlaces());
+			} else if Fraction.len() > Currency.DecimalPlaces() {
+				return Err(MoneroRequestError::InvalidInput(match Currency {
+					Currency::USD => "Too many decimal places for USD",
+					Currency::XMR => "Too many decimal places for XMR"
+				}));
+			}
+		}
+
+		// Amounts above the total supply are a unit mix-up. Compared on the digits, so a fraction of a piconero over the
+		// ceiling still counts; a whole part too long for a u128 is over it by far.
+		if Currency == Currency::XMR {
+			if let Ok((Whole, Fraction)) = SplitAmount(&self.Amount) {
+				let Over = match Whole.parse::<u128>() {
+					Ok(Whole) => Whole > Options.MaxXMRAmount as u128 || (Whole == Options.MaxXMRAmount as u128 && Fraction.bytes().any(|B| B != b'0')),
+					Err(_) => true
+				};
+				if Over { return Err(MoneroRequestError::InvalidInput("XMR amount exceeds plausible maximum")); }
+			}
+		}
 
-		// NumberOfPayments
-		// Any u8 will be valid.
+		// Billing terms
+		// Both fields are always present, so coherence comes down to their values. A subscription (any NumberOfPayments
+		// other than 1, with 0 running indefinitely) needs a cycle to space its payments, so a zero cycle contradicts it.
+		// A one-time payment ignores the cycle, but the standard still requires a non-zero one; a cycle other than the
+		// default there is only suspicious, see Lint::BillingCycleIgnored.
+		if self.DaysPerBillingCycle == 0 {
+			return Err(MoneroRequestError::InvalidInput(match self.NumberOfPayments {
+				1 => "DaysPerBillingCycle cannot be zero.",
+				_ => "DaysPerBillingCycle cannot be zero for a subscription."
+			}));
+		}
+		if self.NumberOfPayments as u32 > Options.MaxNumberOfPayments {
+			return Err(MoneroRequestError::InvalidInput("NumberOfPayments exceeds maximum"));
+		}
 
 		// ChangeIndicatorURL
+		if self.ChangeIndicatorURL.len() > MAX_URL_BYTES { return Err(MoneroRequestError::InvalidInput("ChangeIndicatorURL exceeds 512 bytes")); }
 		if self.ChangeIndicatorURL.len() > 0 {
 			match url::Url::parse(&self.ChangeIndicatorURL) {
 				Err(e) => return Err(MoneroRequestError::UrlError(e)),
-				Ok(r) => if r.cannot_be_a_base() { return Err(MoneroRequestError::InvalidInput("ChangeIndicatorURL is an invalid URL.")); }
+				Ok(r) => {
+					if r.cannot_be_a_base() { return Err(MoneroRequestError::InvalidInput("ChangeIndicatorURL is an invalid URL.")); }
+					// An allowlist rather than a blocklist, so schemes like javascript: and data: can never get through
+					#[cfg(feature = "test-networks")]
+					let AllowHttp = Options.AllowHttpChangeIndicatorURL;
+					#[cfg(not(feature = "test-networks"))]
+					let AllowHttp = false;
+					if !(r.scheme() == "https" || (AllowHttp && r.scheme() == "http")) {
+						return Err(MoneroRequestError::InvalidInput("ChangeIndicatorURL must use https"));
+					}
+					if Options.RequireDomainChangeIndicatorURL && !matches!(r.host(), Some(url::Host::Domain(_))) {
+						return Err(MoneroRequestError::InvalidInput("ChangeIndicatorURL must use a domain name"));
+					}
+				}
 			};
 		}
 
 		// Version
-		match self.Version.as_str() {
-			"" => self.Version = "1".to_string(),
-			"1" => {},
-			_ => return Err(MoneroRequestError::InvalidInput("Unsupported version."))
-		}
+		if self.Version.is_empty() { self.Version = LATEST_VERSION.to_string(); }
+		if !SUPPORTED_VERSIONS.contains(&self.Version.as_str()) { return Err(MoneroRequestError::InvalidInput("Unsupported version.")); }
 
 		Ok(())
 	}
@@ -114,112 +699,191 @@ impl MoneroRequest {
 
 
 
-/// Accepts a valid Monero Payment Request and returns a [`MoneroRequest`] containing the decoded information.
-pub fn DecodePaymentRequest(Request: String) -> Result<MoneroRequest, MoneroRequestError> {
-	// Parse and validate input
-	let Caps = match regex::Regex::new(r"(?m)(?<header>monero-request):(?<version>\d):(?<request>.+)").unwrap().captures(&Request) {
-		Some(r) => r,
-		None => { return Err(MoneroRequestError::InvalidInput("Invalid request string.")); }
-	};
+/// Returns the most fractional digits an [`Amount`](MoneroRequest::Amount) may have for the given currency code, or `None`
+/// if the currency isn't supported. See [`Currency::DecimalPlaces`].
+pub fn MaxDecimalPlaces(Code: &str) -> Option<usize> {
+	Currency::FromIsoCode(Code).ok().map(|C| C.DecimalPlaces())
+}
 
-	if &Caps["header"] != "monero-request" { return Err(MoneroRequestError::InvalidInput("Invalid request header.")); }
-	if &Caps["version"] != "1" { return Err(MoneroRequestError::InvalidInput("Invalid request version.")); }
-	let Request = &Caps["request"];
+// Splits a plain decimal amount into its whole and fractional digits, dropping any commas grouping the whole part, e.g.
+// `1,234.5` into `1234` and `5`. Errors unless both are ASCII digits and at least one of them is non-empty.
+fn SplitAmount(Amount: &str) -> Result<(String, String), MoneroRequestError> {
+	let Amount = Amount.replace(',', "");
+	let (Whole, Fraction) = Amount.split_once('.').unwrap_or((&Amount, ""));
 
-	// Un-Base64 to GZip
-	let Request = match base64::engine::general_purpose::STANDARD.decode(Request) {
-		Ok(r) => r,
-		Err(e) => return Err(MoneroRequestError::Base64Error(e))
-	};
+	if Whole.is_empty() && Fraction.is_empty() { return Err(MoneroRequestError::InvalidInput("Invalid Amount.")); }
+	if !Whole.chars().chain(Fraction.chars()).all(|C| C.is_ascii_digit()) { return Err(MoneroRequestError::InvalidInput("Invalid Amount.")); }
 
-	// Un-GZip to JSON
-	let mut GZipOutput = GzDecoder::new(Vec::new());
-	if GZipOutput.write_all(&Request).is_err() { return Err(MoneroRequestError::GZipError("Error decompressing date")); }
-	let GZipOutput = match GZipOutput.finish() {
-		Err(_) => return Err(MoneroRequestError::GZipError("Error decompressing date.")),
-		Ok(r) => r
-	};
-	let Request = match String::from_utf8(GZipOutput) {
-		Ok(r) => r,
-		Err(_) => return Err(MoneroRequestError::GZipError("Error decompressing date."))
-	};
+	return Ok((Whole.to_string(), Fraction.to_string()));
+}
 
-	// Deserialize to struct
-	// If the json is not properly formatted, or if fields are missing, this is going to have errors. Improper
-	// json is probably ok to fail on. No fields are marked optional in the spec, so this breaking for a missing 
-	// field should also be ok.
+// The amount patterns, compiled on first use and then shared, including across EncodePar's threads. An amount with commas
+// must group its whole part in threes, and every amount must be plain ASCII digits with an optional fraction.
+static GROUPED_AMOUNT: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^[0-9]{1,3}(,[0-9]{3})+(\.[0-9]*)?$").unwrap());
+static PLAIN_AMOUNT: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^[0-9][0-9,]*(\.[0-9]+)?$").unwrap());
+
+// Rounds a plain `<whole>.<fraction>` amount half up to the given number of decimal places. Any commas grouping the
+// whole part are kept, and regrouped if rounding carries into a new digit, e.g. `999,999.999` to `1,000,000.00`.
+fn RoundAmountHalfUp(Whole: &str, Fraction: &str, Places: usize) -> String {
+	let mut Digits: Vec<u8> = Whole.bytes().filter(|B| *B != b',').chain(Fraction.bytes().take(Places)).collect();
+
+	if Fraction.as_bytes()[Places] >= b'5' {
+		// Carry from the last kept digit leftwards, turning 9s into 0s
+		let mut Carry = true;
+		for D in Digits.iter_mut().rev() {
+			if *D == b'9' { *D = b'0'; } else { *D += 1; Carry = false; break; }
+		}
+		if Carry { Digits.insert(0, b'1'); }
+	}
 
-	let Request: MoneroRequest = match serde_json::from_str(Request.as_str()) {
-		Ok(r) => r,
-		Err(e) => return Err(MoneroRequestError::SerdeError(e))
-	};
+	// The digits are all ASCII, so this can't fail
+	let Digits = String::from_utf8(Digits).unwrap();
+	let (Rounded, Fraction) = Digits.split_at(Digits.len() - Places);
 
-	return Ok(Request);
+	if !Whole.contains(',') { return format!("{}.{}", Rounded, Fraction); }
+
+	let Groups: Vec<&str> = Rounded.as_bytes().rchunks(3).rev().map(|G| std::str::from_utf8(G).unwrap()).collect();
+	return format!("{}.{}", Groups.join(","), Fraction);
 }
 
+// Checks a non-empty PaymentID is 16 hex characters, in either case.
+fn ValidatePaymentID(PaymentID: &str) -> Result<(), MoneroRequestError> {
+	if PaymentID.len() != PAYMENT_ID_BYTES * 2 { return Err(MoneroRequestError::InvalidInput("Invalid PaymentID length.")); }
 
-/// Accepts a [`MoneroRequest`] struct, validates it, and outputs a String constituting a valid Monero Payment Request
-///
-/// If no [`PaymentID`](MoneroRequest::PaymentID) is provided, one will be generated randomly using [`GenRandomPaymentID`]. If you explicitely
-/// do not want to have a PaymentID you may set it to `0000000000000000` (16 zeroes).
-///
-/// If no [`CustomLabel`](MoneroRequest::CustomLabel) is provided `Monero Payment Request` will be used.
-///
-/// [`DaysPerBillingCycle`](MoneroRequest::DaysPerBillingCycle) may not be zero.
-///
-/// [`NumberOfPayments`](MoneroRequest::NumberOfPayments) may not be zero.
-///
-/// If no [`StartDate`](MoneroRequest::StartDate) is provided, now will be used. StartDate should be either in UTC time or contain enough 
-/// information to be parsed into UTC time. The Chrono library is used for this parsing. To guarentee compatibility 
-/// with this MoneroRequest library use Chrono's DateTime type.
-///
-/// If [`Version`](MoneroRequest::Version) is blank the latest version will be used.
-pub fn EncodePaymentRequest(mut Request: MoneroRequest) -> Result<String, MoneroRequestError> {
-	// Validate input
-	if let Err(e) = Request.Validate() { return Err(e); }
-
-	// Serialize to JSON
-	let Output = match serde_json::to_string(&Request) {
-		Ok(o) => o,
-		Err(e) => return Err(MoneroRequestError::SerdeError(e))
-	};
+	for C in PaymentID.chars() {
+		match C {
+			'0'..='9' | 'a'..='f' | 'A'..='F' => {}
+			_ => return Err(MoneroRequestError::InvalidInput("Invalid character in PaymentID."))
+		}
+	}
 
-	// GZip the JSON
-	let mut GZipOutput = GzEncoder::new(Vec::new(), Compression::default());
-	if GZipOutput.write_all(Output.as_bytes()).is_err() { return Err(MoneroRequestError::GZipError("Error compressing data."))}
-	let GZipOutput = match GZipOutput.finish() {
-		Ok(r) => r,
-		Err(_) => return Err(MoneroRequestError::GZipError("Error compressing data."))
-	};
+	Ok(())
+}
 
-	// Base64 the GZip -- This cannot fail? Sus.
-	let Output = base64::engine::general_purpose::STANDARD.encode(GZipOutput);
+// Reads an Amount given as either a JSON string or a JSON number, as some implementations don't quote it. Numbers are
+// kept as their decimal text exactly as written, e.g. `1.5` becomes `"1.5"` and `12345678.123456789` keeps every
+// digit, and are validated like any other Amount.
+fn DeserializeAmount<'de, D: serde::Deserializer<'de>>(Deserializer: D) -> Result<String, D::Error> {
+	DeserializeAmountCow(Deserializer).map(Cow::into_owned)
+}
 
-	// Add tags
-	let Output = format!("monero-request:1:{Output}");
+// Same as DeserializeAmount, but borrows the string from the input when it can.
+pub(crate) fn DeserializeAmountCow<'de, D: serde::Deserializer<'de>>(Deserializer: D) -> Result<Cow<'de, str>, D::Error> {
+	struct AmountVisitor;
 
-	return Ok(Output);
+	impl<'de> serde::de::Visitor<'de> for AmountVisitor {
+		type Value = Cow<'de, str>;
+
+		fn expecting(&self, Formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+			Formatter.write_str("an amount as a string or a number")
+		}
+
+		fn visit_borrowed_str<E: serde::de::Error>(self, Value: &'de str) -> Result<Self::Value, E> { Ok(Cow::Borrowed(Value)) }
+		fn visit_str<E: serde::de::Error>(self, Value: &str) -> Result<Self::Value, E> { Ok(Cow::Owned(Value.to_string())) }
+		fn visit_string<E: serde::de::Error>(self, Value: String) -> Result<Self::Value, E> { Ok(Cow::Owned(Value)) }
+		fn visit_u64<E: serde::de::Error>(self, Value: u64) -> Result<Self::Value, E> { Ok(Cow::Owned(Value.to_string())) }
+		fn visit_i64<E: serde::de::Error>(self, Value: i64) -> Result<Self::Value, E> { Ok(Cow::Owned(Value.to_string())) }
+		fn visit_f64<E: serde::de::Error>(self, Value: f64) -> Result<Self::Value, E> { Ok(Cow::Owned(Value.to_string())) }
+
+		// serde_json's arbitrary_precision hands JSON numbers over as their source text, wrapped in a map, so they never
+		// pass through an f64. Other formats' floats are already binary, so visit_f64 above loses nothing for them.
+		#[cfg(feature = "codec")]
+		fn visit_map<A: serde::de::MapAccess<'de>>(self, Map: A) -> Result<Self::Value, A::Error> {
+			let Number: serde_json::Number = Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(Map))?;
+			Ok(Cow::Owned(Number.to_string()))
+		}
+	}
+
+	Deserializer.deserialize_any(AmountVisitor)
 }
 
 
 
 /// Generates a random string that may be used as a Monero protocol payment_id in an integrated address.
 ///
-/// Returns 16 hex characters as a string. Example: `60B6A010501201F1`
+/// Returns 16 lowercase hex characters as a string. Example: `60b6a010501201f1`. Use [`GenRandomPaymentIDWithCase`]
+/// for uppercase.
 ///
 /// This will be used by default if no [`PaymentID`](MoneroRequest::PaymentID) is provided when encoding a 
 /// new request with [`EncodePaymentRequest`]. You may want to use this directly before calling [`EncodePaymentRequest`] 
 /// so you can check it for uniqueness against prior transactions in your records.
 pub fn GenRandomPaymentID() -> String {
-	let mut RNG = rand::thread_rng();
-	let HEXChars = "0123456789abcdef";
+	return GenRandomPaymentIDWithCase(HexCase::Lower);
+}
+
+
+/// Same as [`GenRandomPaymentID`], but with the hex characters in the given case. Example: `60B6A010501201F1`
+pub fn GenRandomPaymentIDWithCase(Case: HexCase) -> String {
+	return GenRandomHex(PAYMENT_ID_BYTES, Case);
+}
+
+
+/// Same as [`GenRandomPaymentID`], but starting with the fixed hex `Prefix`, e.g. `ac` for `ac3f61b20e9d4477`, so a
+/// merchant's ids stand out in logs. The prefix is lowercased, and may be up to [`MAX_PAYMENT_ID_PREFIX_LEN`]
+/// characters so that at least 8 random hex characters (32 bits) remain. With the longest prefix, two ids share a
+/// value with even odds after about 77,000 ids, so check for collisions when generating that many; each character
+/// shorter multiplies that count by 4.
+pub fn GenRandomPaymentIDWithPrefix(Prefix: &str) -> Result<String, MoneroRequestError> {
+	if Prefix.len() > MAX_PAYMENT_ID_PREFIX_LEN { return Err(MoneroRequestError::InvalidInput("PaymentID prefix is too long.")); }
+	if !Prefix.bytes().all(|B| B.is_ascii_hexdigit()) { return Err(MoneroRequestError::InvalidInput("PaymentID prefix is not hex.")); }
+
+	let Output = Prefix.to_ascii_lowercase() + &GenRandomHexWith(&mut rand::thread_rng(), PAYMENT_ID_BYTES * 2 - Prefix.len(), HexCase::Lower);
+
+	return Ok(Output);
+}
+
+
+/// Same as [`GenRandomPaymentID`], but `Bytes` bytes long (twice as many hex characters) instead of the standard 8.
+///
+/// This is for testing and forward compatibility experiments only. Validation still requires the standard 16 hex
+/// characters, so requests with an id of any other length won't encode.
+pub fn GenRandomPaymentIDOfLength(Bytes: usize) -> String {
+	return GenRandomHex(Bytes, HexCase::Lower);
+}
+
+
+/// Returns a valid PaymentID derived from `Seed`, the same for a given seed across runs, platforms and versions of this
+/// crate, for snapshot tests. Different seeds give unrelated looking ids. Only available with the `test-utils` feature.
+#[cfg(feature = "test-utils")]
+pub fn FixedPaymentID(Seed: u64) -> String {
+	// SplitMix64's finalizer, a fixed bijection, so the ids never change and no two seeds share one
+	let mut Output = Seed.wrapping_add(0x9e3779b97f4a7c15);
+	Output = (Output ^ (Output >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+	Output = (Output ^ (Output >> 27)).wrapping_mul(0x94d049bb133111eb);
+	Output ^= Output >> 31;
+
+	return format!("{Output:016x}");
+}
+
+
+// The length of a payment id in bytes, as used in integrated addresses. 16 hex characters.
+const PAYMENT_ID_BYTES: usize = 8;
+
+fn GenRandomHex(Bytes: usize, Case: HexCase) -> String {
+	return GenRandomHexWith(&mut rand::thread_rng(), Bytes * 2, Case);
+}
+
+fn GenRandomHexWith<R: Rng>(RNG: &mut R, Chars: usize, Case: HexCase) -> String {
+	let HEXChars: &[u8; 16] = match Case {
+		HexCase::Lower => b"0123456789abcdef",
+		HexCase::Upper => b"0123456789ABCDEF"
+	};
+
+	// Indexing within the table's own length, so this can't panic whatever the table holds
+	let Output: String = iter::repeat_with(|| HEXChars[RNG.gen_range(0..HEXChars.len())] as char).take(Chars).collect();
 
-	let Output: String = iter::repeat_with(|| HEXChars.chars().nth(RNG.gen_range(0..=15)).unwrap()).take(16).collect();
-	
 	return Output;
 }
 
 
+/// The letter case of hex characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HexCase {
+	Lower,
+	Upper
+}
+
+
 
 /// Enum containing all errors we may emit.
 #[derive(thiserror::Error, Debug)]
@@ -227,23 +891,81 @@ pub enum MoneroRequestError {
 	#[error("{0}")]
 	InvalidInput(&'static str),
 
+	#[cfg(feature = "codec")]
 	#[error(transparent)]
 	SerdeError(#[from] serde_json::Error),
 
+	/// Required fields left empty, see [`MoneroRequest::Normalized`]: the [`SellersWallet`](MoneroRequest::SellersWallet)
+	/// and [`Currency`](MoneroRequest::Currency), and the [`Amount`](MoneroRequest::Amount) for subscriptions in currencies
+	/// that need one, see [`ValidationOptions::AmountRequiredForSubscriptions`]. Otherwise an empty Amount makes an open
+	/// amount request, and the other fields have defaults.
+	#[error("Missing required fields: {}", .0.iter().map(Field::Name).collect::<Vec<_>>().join(", "))]
+	MissingFields(Vec<Field>),
+
 	#[error(transparent)]
 	ChronoError(#[from] chrono::ParseError),
 
 	#[error(transparent)]
 	UrlError(#[from] url::ParseError),
 
+	#[error(transparent)]
+	IoError(#[from] std::io::Error),
+
+	#[cfg(feature = "codec")]
 	#[error(transparent)]
 	Base64Error(#[from] base64::DecodeError),
 
+	#[cfg(feature = "cbor")]
+	#[error(transparent)]
+	CborSerializeError(#[from] ciborium::ser::Error<std::io::Error>),
+
+	#[cfg(feature = "cbor")]
+	#[error(transparent)]
+	CborDeserializeError(#[from] ciborium::de::Error<std::io::Error>),
+
+
+	#[cfg(feature = "rayon")]
+	#[error("PaymentID {PaymentID} is used by both request {First} and request {Second}")]
+	DuplicatePaymentID { PaymentID: String, First: usize, Second: usize },
 
+	#[cfg(feature = "qr")]
+	#[error(transparent)]
+	QRError(#[from] qrcode::types::QrError),
+
+	#[cfg(feature = "codec")]
 	#[error("{0}")]
 	GZipError(&'static str)
 }
 
+impl MoneroRequestError {
+	/// Converts to an [`OwnedError`], which is `Clone`, for handing the same error to several consumers or storing it.
+	/// Variants wrapping another library's error keep only its message.
+	pub fn ToOwnedError(&self) -> OwnedError {
+		match self {
+			MoneroRequestError::InvalidInput(Message) => OwnedError::InvalidInput(Message),
+			#[cfg(feature = "codec")]
+			MoneroRequestError::GZipError(Message) => OwnedError::GZipError(Message),
+			Other => OwnedError::Other(Other.to_string())
+		}
+	}
+}
+
+/// A `Clone + Send + Sync` copy of a [`MoneroRequestError`], made with [`MoneroRequestError::ToOwnedError`]. Messages
+/// display the same as the original error's.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum OwnedError {
+	#[error("{0}")]
+	InvalidInput(&'static str),
+
+	#[cfg(feature = "codec")]
+	#[error("{0}")]
+	GZipError(&'static str),
+
+	/// Any variant wrapping another library's error, by its message.
+	#[error("{0}")]
+	Other(String)
+}
+
 
 
 
@@ -0,0 +1,73 @@
+//! Non-fatal warnings about requests that are valid but probably not what was intended.
+use std::{fmt, sync::LazyLock};
+use crate::MoneroRequest;
+
+
+
+/// A warning about a valid request, returned by [`MoneroRequest::Lint`]. Lints never block encoding; they're meant to be
+/// shown in a dev console or a merchant's dashboard.
+///
+/// More lints may be added in future releases, so match with a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Lint {
+	/// The [`CustomLabel`](MoneroRequest::CustomLabel) contains what looks like an email address. Labels are visible to
+	/// anyone who sees the code, so customer details don't belong there.
+	LabelContainsEmail,
+	/// The [`CustomLabel`](MoneroRequest::CustomLabel) contains a run of 6 or more digits, which is often an order,
+	/// account, or phone number.
+	LabelContainsLongNumber,
+	/// The [`PaymentID`](MoneroRequest::PaymentID) is all zeroes, e.g. [`NO_PAYMENT_ID`](crate::NO_PAYMENT_ID), so
+	/// payments to this request can't be told apart from other payments without a payment id.
+	PaymentIDAllZeroes,
+	/// The [`Amount`](MoneroRequest::Amount) is in USD but has no cents, e.g. `10` rather than `10.00`.
+	AmountHasNoDecimalsForUSD,
+	/// A one-time payment (a [`NumberOfPayments`](MoneroRequest::NumberOfPayments) of 1) has a
+	/// [`DaysPerBillingCycle`](MoneroRequest::DaysPerBillingCycle) other than the default 30. The cycle has no effect on
+	/// a single payment, so this is usually a subscription with its number of payments left out.
+	BillingCycleIgnored
+}
+
+impl Lint {
+	/// A short, human readable description of the lint.
+	pub fn Message(&self) -> &'static str {
+		match self {
+			Lint::LabelContainsEmail => "label contains what looks like an email",
+			Lint::LabelContainsLongNumber => "label contains what looks like an order or phone number",
+			Lint::PaymentIDAllZeroes => "payment id is all zeroes",
+			Lint::AmountHasNoDecimalsForUSD => "amount has no decimals for USD",
+			Lint::BillingCycleIgnored => "billing cycle is set but there's only one payment"
+		}
+	}
+}
+
+impl fmt::Display for Lint {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.Message()) }
+}
+
+
+
+// Compiled on first use and then shared across threads
+static EMAIL: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"[^\s@]+@[^\s@]+\.[^\s@]+").unwrap());
+static LONG_NUMBER: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"\d{6,}").unwrap());
+
+
+
+impl MoneroRequest {
+	/// Checks the request for things that are valid but probably a mistake, see [`Lint`]. This complements validation
+	/// and doesn't repeat it, so an invalid request may return no lints.
+	pub fn Lint(&self) -> Vec<Lint> {
+		let mut Output = Vec::new();
+
+		if EMAIL.is_match(&self.CustomLabel) { Output.push(Lint::LabelContainsEmail); }
+		if LONG_NUMBER.is_match(&self.CustomLabel) { Output.push(Lint::LabelContainsLongNumber); }
+
+		if !self.PaymentID.is_empty() && self.PaymentID.chars().all(|C| C == '0') { Output.push(Lint::PaymentIDAllZeroes); }
+
+		if self.Currency == "USD" && !self.IsOpenAmount() && !self.Amount.contains('.') { Output.push(Lint::AmountHasNoDecimalsForUSD); }
+
+		if self.NumberOfPayments == 1 && self.DaysPerBillingCycle != 30 { Output.push(Lint::BillingCycleIgnored); }
+
+		return Output;
+	}
+}
@@ -0,0 +1,66 @@
+//! A smaller, non-standard encoding that leaves out fields holding values a decoder can re-derive.
+//!
+//! The Monero Payment Request Standard marks every field as required, so minified codes only decode with
+//! [`DecodeMinified`]. Other implementations will reject them.
+use serde_json::Value;
+use crate::{DecodeInFormat, EncodeInFormat, MoneroRequest, MoneroRequestError, PayloadFormat, PayloadJson, DEFAULT_LABEL};
+
+
+
+/// Same as [`EncodePaymentRequest`](crate::EncodePaymentRequest), but leaves the following fields out of the payload
+/// for a smaller code:
+/// - [`CustomLabel`](MoneroRequest::CustomLabel) when it's [`DEFAULT_LABEL`].
+/// - [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL) when it's empty.
+///
+/// All other fields are always included. Decode with [`DecodeMinified`].
+pub fn EncodeMinified(Request: MoneroRequest) -> Result<String, MoneroRequestError> {
+	let Request = Request.Normalized()?;
+
+	return EncodeInFormat(&MinifiedFormat, &Request);
+}
+
+
+/// Decodes a code produced by [`EncodeMinified`], filling any elided fields back in with their defaults. Complete,
+/// standard codes decode too.
+pub fn DecodeMinified(Request: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	return DecodeInFormat(&MinifiedFormat, Request);
+}
+
+
+// The standard's JSON payload, minus the fields EncodeMinified leaves out.
+struct MinifiedFormat;
+
+impl PayloadFormat for MinifiedFormat {
+	fn Serialize(&self, Request: &MoneroRequest) -> Result<Vec<u8>, MoneroRequestError> {
+		let mut Json = PayloadJson(Request)?;
+
+		// This unwrap is safe as the payload is always an object
+		let Object = Json.as_object_mut().unwrap();
+		if Request.CustomLabel == DEFAULT_LABEL { Object.remove("custom_label"); }
+		if Request.ChangeIndicatorURL.is_empty() { Object.remove("change_indicator_url"); }
+
+		match serde_json::to_vec(&Json) {
+			Ok(r) => Ok(r),
+			Err(e) => Err(MoneroRequestError::SerdeError(e))
+		}
+	}
+
+	fn Deserialize(&self, Payload: &[u8]) -> Result<MoneroRequest, MoneroRequestError> {
+		let mut Json: Value = match serde_json::from_slice(Payload) {
+			Ok(r) => r,
+			Err(e) => return Err(MoneroRequestError::SerdeError(e))
+		};
+
+		let Object = match Json.as_object_mut() {
+			Some(r) => r,
+			None => return Err(MoneroRequestError::InvalidInput("Invalid request payload."))
+		};
+		Object.entry("custom_label").or_insert(DEFAULT_LABEL.into());
+		Object.entry("change_indicator_url").or_insert("".into());
+
+		match serde_json::from_value(Json) {
+			Ok(r) => Ok(r),
+			Err(e) => Err(MoneroRequestError::SerdeError(e))
+		}
+	}
+}
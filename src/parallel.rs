@@ -0,0 +1,39 @@
+//! Encoding in bulk, spread across a thread pool.
+use std::collections::HashMap;
+use rayon::prelude::*;
+use crate::{EncodePaymentRequest, MoneroRequest, MoneroRequestError, NO_PAYMENT_ID};
+
+
+
+/// Encodes every request with [`EncodePaymentRequest`] in parallel on rayon's global thread pool, returning the results
+/// in the same order as `Requests`. Encoding is CPU bound (mostly gzip), so for large batches, such as onboarding many
+/// merchants at once, this scales with the number of cores. A failed request doesn't stop the others.
+pub fn EncodePar(Requests: &[MoneroRequest]) -> Vec<Result<String, MoneroRequestError>> {
+	return Requests.par_iter().map(|Request| EncodePaymentRequest(Request.clone())).collect();
+}
+
+
+/// Same as [`EncodePar`], but first checks that no two requests end up with the same
+/// [`PaymentID`](MoneroRequest::PaymentID), as payments to them couldn't be told apart. The first collision found is
+/// returned as [`MoneroRequestError::DuplicatePaymentID`] and nothing is encoded.
+///
+/// Ids are compared after validation, so generated ids and integrated addresses' ids are included. [`NO_PAYMENT_ID`]
+/// means no id at all and may be shared. Requests that fail validation are left out of the check and fail in the output
+/// as usual.
+pub fn EncodeParUnique(Requests: &[MoneroRequest]) -> Result<Vec<Result<String, MoneroRequestError>>, MoneroRequestError> {
+	let Normalized: Vec<Result<MoneroRequest, MoneroRequestError>> = Requests.par_iter().map(|Request| Request.clone().Normalized()).collect();
+
+	let mut Seen = HashMap::new();
+	for (Index, Request) in Normalized.iter().enumerate() {
+		let Request = match Request {
+			Ok(r) if r.PaymentID != NO_PAYMENT_ID => r,
+			_ => continue
+		};
+
+		if let Some(First) = Seen.insert(Request.PaymentID.as_str(), Index) {
+			return Err(MoneroRequestError::DuplicatePaymentID { PaymentID: Request.PaymentID.clone(), First, Second: Index });
+		}
+	}
+
+	return Ok(Normalized.into_par_iter().map(|Request| EncodePaymentRequest(Request?)).collect());
+}
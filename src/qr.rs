@@ -0,0 +1,21 @@
+//! QR code rendering of encoded Monero Payment Requests.
+use qrcode::{render::unicode, QrCode};
+use crate::MoneroRequestError;
+
+
+
+/// Renders an encoded request (as returned by [`EncodePaymentRequest`](crate::EncodePaymentRequest)) as a QR code made
+/// of unicode block characters, suitable for printing to a terminal.
+pub fn RenderQRCode(Request: &str) -> Result<String, MoneroRequestError> {
+	let Code = match QrCode::new(Request.as_bytes()) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::QRError(e))
+	};
+
+	let Output = Code.render::<unicode::Dense1x2>()
+		.dark_color(unicode::Dense1x2::Light)
+		.light_color(unicode::Dense1x2::Dark)
+		.build();
+
+	return Ok(Output);
+}
@@ -0,0 +1,42 @@
+//! Matching payments seen by a wallet back to the requests they pay.
+use crate::{IntegratedPaymentID, MoneroRequest};
+
+
+
+/// A payment as reported by a wallet, e.g. from `monero-wallet-rpc`'s `get_transfers`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedPayment {
+	/// The address the payment was sent to.
+	pub Address: String,
+	/// The payment id it carried, in either case.
+	pub PaymentID: String,
+	/// The amount received, in piconeros.
+	pub AmountAtomic: u64
+}
+
+impl MoneroRequest {
+	/// Returns true if `Payment` pays this request: it went to the [`SellersWallet`](MoneroRequest::SellersWallet), with
+	/// the request's payment id, and for at least the requested amount. For an integrated address the payment id is
+	/// the one embedded in the address.
+	///
+	/// An open amount accepts any amount. Amounts can only be compared for XMR requests, so a request in any other
+	/// currency never matches; convert it to an XMR request at the agreed rate first.
+	pub fn MatchesPayment(&self, Payment: &ReceivedPayment) -> bool {
+		if Payment.Address != self.SellersWallet { return false; }
+
+		let Expected = match IntegratedPaymentID(&self.SellersWallet) {
+			Ok(Some(r)) => r,
+			Ok(None) => self.PaymentID.clone(),
+			Err(_) => return false
+		};
+		if !Payment.PaymentID.eq_ignore_ascii_case(&Expected) { return false; }
+
+		if self.Currency != "XMR" { return false; }
+		if self.IsOpenAmount() { return true; }
+
+		match self.AmountAtomic() {
+			Ok(r) => Payment.AmountAtomic >= r,
+			Err(_) => false
+		}
+	}
+}
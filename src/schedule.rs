@@ -0,0 +1,113 @@
+//! Payment schedules: when each payment of a request falls due.
+use chrono::{DateTime, Duration, Utc};
+use crate::{MoneroRequest, MoneroRequestError};
+
+
+
+/// Where a request's time window stands at a given moment, see [`MoneroRequest::Status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RequestStatus {
+	/// [`StartDate`](MoneroRequest::StartDate) is still to come.
+	NotStarted,
+	/// Started and not yet over.
+	Active,
+	/// The last billing cycle has ended.
+	Completed
+}
+
+
+
+impl MoneroRequest {
+	/// Returns the due date of every payment: the first at [`StartDate`](MoneroRequest::StartDate), then one every
+	/// [`DaysPerBillingCycle`](MoneroRequest::DaysPerBillingCycle) days, [`NumberOfPayments`](MoneroRequest::NumberOfPayments)
+	/// in total.
+	///
+	/// A NumberOfPayments of zero means the subscription runs indefinitely, which has no complete schedule and is an
+	/// error.
+	pub fn PaymentSchedule(&self) -> Result<Vec<DateTime<Utc>>, MoneroRequestError> {
+		if self.NumberOfPayments == 0 { return Err(MoneroRequestError::InvalidInput("Payment schedule is indefinite.")); }
+
+		return Ok(self.PaymentScheduleIter()?.collect());
+	}
+
+	/// Same as [`PaymentSchedule`](MoneroRequest::PaymentSchedule), but yields the due dates one at a time rather than
+	/// building them all up front. An indefinite subscription isn't an error here: its dates just never run out, so take
+	/// as many as needed.
+	pub fn PaymentScheduleIter(&self) -> Result<impl Iterator<Item = DateTime<Utc>>, MoneroRequestError> {
+		let (Start, Cycle) = self.StartAndCycle()?;
+		let Count = match self.NumberOfPayments {
+			0 => None,
+			N => Some(N as usize)
+		};
+
+		// Stops early rather than overflowing once the dates pass the last representable one
+		let Output = (0..).map_while(move |I| Start.checked_add_signed(Cycle * I)).take(Count.unwrap_or(usize::MAX));
+
+		return Ok(Output);
+	}
+
+	/// Returns the due dates in [`PaymentSchedule`](MoneroRequest::PaymentSchedule) that are on or after `Now`, i.e. the
+	/// payments still to come. Empty once the last payment is due.
+	///
+	/// Errors the same way as PaymentSchedule, including for indefinite subscriptions.
+	pub fn RemainingPayments(&self, Now: DateTime<Utc>) -> Result<Vec<DateTime<Utc>>, MoneroRequestError> {
+		let Output = self.PaymentSchedule()?.into_iter().filter(|D| *D >= Now).collect();
+
+		return Ok(Output);
+	}
+
+	/// Returns the first due date strictly after `Now`, or `None` if the last payment is already due. Indefinite
+	/// subscriptions (a [`NumberOfPayments`](MoneroRequest::NumberOfPayments) of zero) always have a next date, unless
+	/// it would fall past the last representable date, which is also `None`.
+	///
+	/// Unlike [`RemainingPayments`](MoneroRequest::RemainingPayments) this works out the date directly rather than
+	/// building the schedule.
+	pub fn NextPaymentDate(&self, Now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, MoneroRequestError> {
+		let (Start, Cycle) = self.StartAndCycle()?;
+		if Start > Now { return Ok(Some(Start)); }
+
+		// The number of whole cycles elapsed since the start, plus one for the cycle currently underway
+		let Index = (Now - Start).num_seconds() / Cycle.num_seconds() + 1;
+		if self.NumberOfPayments != 0 && Index >= self.NumberOfPayments as i64 { return Ok(None); }
+
+		return Ok(CycleOffset(Cycle, Index).and_then(|Offset| Start.checked_add_signed(Offset)));
+	}
+
+	/// Returns where the request's time window stands at `Now`, e.g. for a dashboard badge. It's
+	/// [`NotStarted`](RequestStatus::NotStarted) before the [`StartDate`](MoneroRequest::StartDate), then
+	/// [`Active`](RequestStatus::Active) until the end of the last billing cycle, then
+	/// [`Completed`](RequestStatus::Completed).
+	///
+	/// This is worked out from the dates only: payment state isn't tracked, so a subscription that was never paid still
+	/// completes on schedule. A one-time payment (a [`NumberOfPayments`](MoneroRequest::NumberOfPayments) of one) has no
+	/// end other than being paid, which the crate can't know, so it stays Active once started, as do indefinite
+	/// subscriptions.
+	pub fn Status(&self, Now: DateTime<Utc>) -> Result<RequestStatus, MoneroRequestError> {
+		let (Start, Cycle) = self.StartAndCycle()?;
+		if Start > Now { return Ok(RequestStatus::NotStarted); }
+		if self.NumberOfPayments <= 1 { return Ok(RequestStatus::Active); }
+
+		// An end past the last representable date is never reached
+		let End = CycleOffset(Cycle, self.NumberOfPayments as i64).and_then(|Offset| Start.checked_add_signed(Offset));
+		if End.is_some_and(|End| Now >= End) { return Ok(RequestStatus::Completed); }
+
+		return Ok(RequestStatus::Active);
+	}
+
+	// Parses the StartDate and the billing cycle length that every schedule calculation starts from.
+	fn StartAndCycle(&self) -> Result<(DateTime<Utc>, Duration), MoneroRequestError> {
+		if self.DaysPerBillingCycle == 0 { return Err(MoneroRequestError::InvalidInput("DaysPerBillingCycle cannot be zero.")) }
+
+		let Start = match self.StartDate.parse::<DateTime<Utc>>() {
+			Ok(r) => r,
+			Err(e) => return Err(MoneroRequestError::ChronoError(e))
+		};
+
+		return Ok((Start, Duration::days(self.DaysPerBillingCycle as i64)));
+	}
+}
+
+// The length of `Count` billing cycles, or `None` if it doesn't fit in a Duration.
+fn CycleOffset(Cycle: Duration, Count: i64) -> Option<Duration> {
+	return Cycle.num_seconds().checked_mul(Count).and_then(Duration::try_seconds);
+}
@@ -0,0 +1,13 @@
+//! JSON Schema of [`MoneroRequest`], for generating and validating forms on web front-ends.
+use crate::MoneroRequest;
+
+
+
+/// Returns a JSON Schema describing a [`MoneroRequest`] as it's serialized in a payload, i.e. with snake_case field
+/// names.
+///
+/// The schema covers types only. Validation rules such as the wallet address checksum aren't expressible in it, so
+/// requests should still be checked with [`MoneroRequest::Normalized`].
+pub fn JsonSchema() -> serde_json::Value {
+	return schemars::schema_for!(MoneroRequest).to_value();
+}
@@ -0,0 +1,56 @@
+//! An async counterpart to [`DecodeLines`](crate::DecodeLines), for services reading codes off a socket.
+use futures::{io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt}, stream, Stream};
+use crate::{DecodePaymentRequest, MoneroRequest, MoneroRequestError, MAX_ENCODED_LENGTH};
+
+
+
+/// Decodes newline-delimited Monero Payment Requests from `Reader` as they arrive, e.g. a socket wrapped in a
+/// `futures::io::BufReader`. The framing is one code per line, ended by `\n` (or `\r\n`) or the end of the input. As
+/// with [`DecodeLines`](crate::DecodeLines), blank lines are skipped, whitespace is trimmed, and a line that fails to
+/// decode yields an `Err` without ending the stream.
+///
+/// At most [`MAX_ENCODED_LENGTH`] bytes (plus the line ending) are buffered per line, so a peer can't exhaust memory by
+/// never sending a newline: a longer line yields an error and is skipped. Each code is then decoded with the usual
+/// decompression limit, [`MAX_PAYLOAD_BYTES`](crate::MAX_PAYLOAD_BYTES). Only available with the `async` feature.
+pub fn DecodeStream<R: AsyncBufRead + Unpin>(Reader: R) -> impl Stream<Item = Result<MoneroRequest, MoneroRequestError>> {
+	stream::unfold(Reader, |mut Reader| async move {
+		loop {
+			match ReadLine(&mut Reader).await {
+				Ok(None) => return None,
+				Ok(Some(Line)) if Line.trim().is_empty() => continue,
+				Ok(Some(Line)) => return Some((DecodePaymentRequest(Line.trim().to_string()), Reader)),
+				Err(e) => return Some((Err(e), Reader))
+			}
+		}
+	})
+}
+
+
+
+// Reads the next line, or None at the end of the input. Reads one byte past the longest code and its line ending, so
+// that an over-long line is caught without buffering all of it.
+async fn ReadLine<R: AsyncBufRead + Unpin>(Reader: &mut R) -> Result<Option<String>, MoneroRequestError> {
+	let Limit = MAX_ENCODED_LENGTH + 3;
+
+	let mut Line = Vec::new();
+	if (&mut *Reader).take(Limit as u64).read_until(b'\n', &mut Line).await? == 0 { return Ok(None); }
+
+	if Line.len() == Limit && !Line.ends_with(b"\n") {
+		SkipLine(Reader).await?;
+		return Err(MoneroRequestError::InvalidInput("Encoded request is too long."));
+	}
+
+	match String::from_utf8(Line) {
+		Ok(r) => return Ok(Some(r)),
+		Err(_) => return Err(MoneroRequestError::InvalidInput("Encoded request is not valid UTF-8."))
+	}
+}
+
+// Discards the rest of the current line, a chunk at a time.
+async fn SkipLine<R: AsyncBufRead + Unpin>(Reader: &mut R) -> Result<(), MoneroRequestError> {
+	let mut Chunk = Vec::new();
+	loop {
+		Chunk.clear();
+		if (&mut *Reader).take(4096).read_until(b'\n', &mut Chunk).await? == 0 || Chunk.ends_with(b"\n") { return Ok(()); }
+	}
+}
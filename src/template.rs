@@ -0,0 +1,58 @@
+//! A flat map of display-ready strings, for filling invoice templates.
+use std::collections::BTreeMap;
+use chrono::{DateTime, Utc};
+use crate::{MaskAddress, MoneroRequest, DEFAULT_LABEL};
+
+
+
+impl MoneroRequest {
+	/// Returns the request as formatted strings under fixed keys, for template engines that take a flat map, e.g.
+	/// `{{amount}} {{term}}`. The values are for showing people, not for parsing back; use
+	/// [`ToCompactParams`](MoneroRequest::ToCompactParams) for a lossless map. The keys won't change:
+	///
+	/// | Key                    | Value                                                                            | Example            |
+	/// |------------------------|----------------------------------------------------------------------------------|--------------------|
+	/// | `label`                | [`CustomLabel`](MoneroRequest::CustomLabel), or [`DEFAULT_LABEL`] if empty       | `Coffee`           |
+	/// | `wallet`               | [`SellersWallet`](MoneroRequest::SellersWallet) shortened with [`MaskAddress`]   | `4At3X5…hCeX2S`    |
+	/// | `amount`               | [`DisplayAmount`](MoneroRequest::DisplayAmount), or `Any amount` if open         | `$1,234.50`        |
+	/// | `currency`             | [`Currency`](MoneroRequest::Currency)                                            | `USD`              |
+	/// | `payment_id`           | [`PaymentID`](MoneroRequest::PaymentID)                                          | `0123456789abcdef` |
+	/// | `start_date`           | [`StartDate`](MoneroRequest::StartDate) as `YYYY-MM-DD`, empty if it won't parse | `2024-01-31`       |
+	/// | `term`                 | `one-time`, `daily`, `weekly`, `monthly`, or `every N days`                      | `monthly`          |
+	/// | `payments`             | [`NumberOfPayments`](MoneroRequest::NumberOfPayments), or `ongoing` if zero      | `12`               |
+	/// | `change_indicator_url` | [`ChangeIndicatorURL`](MoneroRequest::ChangeIndicatorURL)                        | empty              |
+	pub fn ToTemplateMap(&self) -> BTreeMap<&'static str, String> {
+		let Label = if self.CustomLabel.is_empty() { DEFAULT_LABEL.to_string() } else { self.CustomLabel.clone() };
+		let Amount = if self.IsOpenAmount() { "Any amount".to_string() } else { self.DisplayAmount() };
+
+		let StartDate = match self.StartDate.parse::<DateTime<Utc>>() {
+			Ok(r) => r.format("%Y-%m-%d").to_string(),
+			Err(_) => String::new()
+		};
+
+		let Term = match (self.NumberOfPayments, self.DaysPerBillingCycle) {
+			(1, _) => "one-time".to_string(),
+			(_, 1) => "daily".to_string(),
+			(_, 7) => "weekly".to_string(),
+			(_, 30) => "monthly".to_string(),
+			(_, Days) => format!("every {Days} days")
+		};
+
+		let Payments = match self.NumberOfPayments {
+			0 => "ongoing".to_string(),
+			N => N.to_string()
+		};
+
+		return BTreeMap::from([
+			("label", Label),
+			("wallet", MaskAddress(&self.SellersWallet)),
+			("amount", Amount),
+			("currency", self.Currency.clone()),
+			("payment_id", self.PaymentID.clone()),
+			("start_date", StartDate),
+			("term", Term),
+			("payments", Payments),
+			("change_indicator_url", self.ChangeIndicatorURL.clone())
+		]);
+	}
+}
@@ -0,0 +1,217 @@
+//! Validated wrappers around the string fields of a [`MoneroRequest`](crate::MoneroRequest).
+use std::{fmt, ops::Deref, str::FromStr};
+use crate::{MoneroRequestError, ValidatePaymentID, ValidateWalletAddress};
+
+
+
+/// A PaymentID that is known to be valid: 16 hex characters. Either case is accepted and stored lowercase, the same
+/// canonical form [`MoneroRequest::Normalized`](crate::MoneroRequest::Normalized) gives it.
+///
+/// Derefs to `&str`, so it can be passed anywhere a string slice is expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PaymentID(String);
+
+impl TryFrom<&str> for PaymentID {
+	type Error = MoneroRequestError;
+
+	fn try_from(Value: &str) -> Result<Self, Self::Error> {
+		ValidatePaymentID(Value)?;
+
+		Ok(PaymentID(Value.to_ascii_lowercase()))
+	}
+}
+
+impl FromStr for PaymentID {
+	type Err = MoneroRequestError;
+
+	fn from_str(Value: &str) -> Result<Self, Self::Err> { PaymentID::try_from(Value) }
+}
+
+impl AsRef<str> for PaymentID {
+	fn as_ref(&self) -> &str { &self.0 }
+}
+
+impl Deref for PaymentID {
+	type Target = str;
+
+	fn deref(&self) -> &str { &self.0 }
+}
+
+impl fmt::Display for PaymentID {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+
+
+/// A wallet address that is known to pass [`ValidateWalletAddress`].
+///
+/// Derefs to `&str`, so it can be passed anywhere a string slice is expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WalletAddress(String);
+
+impl TryFrom<&str> for WalletAddress {
+	type Error = MoneroRequestError;
+
+	fn try_from(Value: &str) -> Result<Self, Self::Error> {
+		ValidateWalletAddress(Value)?;
+
+		Ok(WalletAddress(Value.to_string()))
+	}
+}
+
+impl FromStr for WalletAddress {
+	type Err = MoneroRequestError;
+
+	fn from_str(Value: &str) -> Result<Self, Self::Err> { WalletAddress::try_from(Value) }
+}
+
+impl AsRef<str> for WalletAddress {
+	fn as_ref(&self) -> &str { &self.0 }
+}
+
+impl Deref for WalletAddress {
+	type Target = str;
+
+	fn deref(&self) -> &str { &self.0 }
+}
+
+impl fmt::Display for WalletAddress {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+
+
+/// A currency a request may be priced in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+	USD,
+	XMR
+}
+
+impl Currency {
+	/// Looks up a currency by its code, e.g. `USD`, in any case. Codes are ISO-4217, except for `XMR` which isn't an
+	/// ISO-4217 code but is what the standard uses for Monero. Unsupported codes (including valid ISO-4217 ones like
+	/// `EUR`) are an error.
+	pub fn FromIsoCode(Code: &str) -> Result<Currency, MoneroRequestError> {
+		match Code.to_ascii_uppercase().as_str() {
+			"USD" => Ok(Currency::USD),
+			"XMR" => Ok(Currency::XMR),
+			_ => Err(MoneroRequestError::InvalidInput("Unsupported currency code."))
+		}
+	}
+
+	/// The currency's code as used in [`Currency`](crate::MoneroRequest::Currency).
+	pub fn IsoCode(&self) -> &'static str {
+		match self {
+			Currency::USD => "USD",
+			Currency::XMR => "XMR"
+		}
+	}
+
+	/// The most fractional digits an [`Amount`](crate::MoneroRequest::Amount) may have in this currency. USD is priced
+	/// in cents, XMR in piconeros (its atomic unit).
+	pub fn DecimalPlaces(&self) -> usize {
+		match self {
+			Currency::USD => 2,
+			Currency::XMR => 12
+		}
+	}
+
+	/// The symbol used when displaying amounts in this currency, see [`MoneroRequest::DisplayAmount`](crate::MoneroRequest::DisplayAmount).
+	pub fn Symbol(&self) -> &'static str {
+		match self {
+			Currency::USD => "$",
+			Currency::XMR => "XMR"
+		}
+	}
+
+	/// Where [`Symbol`](Currency::Symbol) goes relative to the amount.
+	pub fn SymbolPlacement(&self) -> SymbolPlacement {
+		match self {
+			Currency::USD => SymbolPlacement::Prefix,
+			Currency::XMR => SymbolPlacement::Suffix
+		}
+	}
+}
+
+/// Where a currency's symbol is placed when displaying an amount.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymbolPlacement {
+	/// Directly before the amount, e.g. `$123.45`.
+	Prefix,
+	/// After the amount, separated by a space, e.g. `123.45 XMR`.
+	Suffix
+}
+
+impl TryFrom<&str> for Currency {
+	type Error = MoneroRequestError;
+
+	fn try_from(Value: &str) -> Result<Self, Self::Error> { Currency::FromIsoCode(Value) }
+}
+
+impl FromStr for Currency {
+	type Err = MoneroRequestError;
+
+	fn from_str(Value: &str) -> Result<Self, Self::Err> { Currency::FromIsoCode(Value) }
+}
+
+impl From<Currency> for String {
+	fn from(Value: Currency) -> Self { Value.IsoCode().to_string() }
+}
+
+impl fmt::Display for Currency {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.IsoCode()) }
+}
+
+
+
+/// The fields of a [`MoneroRequest`](crate::MoneroRequest).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Field {
+	CustomLabel,
+	SellersWallet,
+	Currency,
+	Amount,
+	PaymentID,
+	StartDate,
+	DaysPerBillingCycle,
+	NumberOfPayments,
+	ChangeIndicatorURL,
+	Version
+}
+
+impl Field {
+	/// Every field, in the order the Monero Payment Request standard lists them. This is not the alphabetical order of [`MoneroRequest`](crate::MoneroRequest)'s fields.
+	pub const ALL: [Field; 10] = [
+		Field::CustomLabel,
+		Field::SellersWallet,
+		Field::Currency,
+		Field::Amount,
+		Field::PaymentID,
+		Field::StartDate,
+		Field::DaysPerBillingCycle,
+		Field::NumberOfPayments,
+		Field::ChangeIndicatorURL,
+		Field::Version
+	];
+
+	/// The field's name, as written in [`MoneroRequest`](crate::MoneroRequest).
+	pub fn Name(&self) -> &'static str {
+		match self {
+			Field::CustomLabel => "CustomLabel",
+			Field::SellersWallet => "SellersWallet",
+			Field::Currency => "Currency",
+			Field::Amount => "Amount",
+			Field::PaymentID => "PaymentID",
+			Field::StartDate => "StartDate",
+			Field::DaysPerBillingCycle => "DaysPerBillingCycle",
+			Field::NumberOfPayments => "NumberOfPayments",
+			Field::ChangeIndicatorURL => "ChangeIndicatorURL",
+			Field::Version => "Version"
+		}
+	}
+}
+
+impl fmt::Display for Field {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.Name()) }
+}
@@ -0,0 +1,66 @@
+//! `monero:` payment URIs, the format understood by most Monero wallets for one-off payments.
+use crate::{MoneroRequest, MoneroRequestError};
+
+
+
+/// Builds a `monero:` URI for the request, e.g. `monero:4At3X5...?tx_payment_id=...&tx_amount=1.5&tx_description=Coffee`.
+///
+/// [`CustomLabel`](MoneroRequest::CustomLabel) is used as the description and [`PaymentID`](MoneroRequest::PaymentID)
+/// as the payment id, each omitted when empty. A `monero:` URI can only express amounts in XMR, so the amount is
+/// omitted for other currencies. Subscription terms have no URI equivalent and are dropped.
+///
+/// Query values are form-encoded, so labels containing reserved characters like `&`, `=`, and `?` survive intact.
+pub fn ToMoneroURI(Request: &MoneroRequest) -> Result<String, MoneroRequestError> {
+	if Request.SellersWallet.is_empty() { return Err(MoneroRequestError::InvalidInput("No seller wallet specified.")); }
+
+	let mut Query = url::form_urlencoded::Serializer::new(String::new());
+	if !Request.PaymentID.is_empty() { Query.append_pair("tx_payment_id", &Request.PaymentID); }
+	if Request.Currency == "XMR" && !Request.Amount.is_empty() { Query.append_pair("tx_amount", &Request.Amount); }
+	if !Request.CustomLabel.is_empty() { Query.append_pair("tx_description", &Request.CustomLabel); }
+	let Query = Query.finish();
+
+	let Output = match Query.is_empty() {
+		true => format!("monero:{}", Request.SellersWallet),
+		false => format!("monero:{}?{Query}", Request.SellersWallet)
+	};
+
+	return Ok(Output);
+}
+
+
+/// Parses a `monero:` URI into a [`MoneroRequest`]. The reverse of [`ToMoneroURI`].
+///
+/// The currency is always XMR. Fields a URI can't express are left empty (or at their one-time payment values) so
+/// that validation fills in the defaults. Unknown parameters are ignored.
+pub fn ParseMoneroURI(URI: &str) -> Result<MoneroRequest, MoneroRequestError> {
+	let URI = match url::Url::parse(URI) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::UrlError(e))
+	};
+
+	if URI.scheme() != "monero" { return Err(MoneroRequestError::InvalidInput("Not a monero: URI.")); }
+
+	let mut Request = MoneroRequest {
+		CustomLabel: "".to_string(),
+		SellersWallet: URI.path().to_string(),
+		Currency: "XMR".to_string(),
+		Amount: "".to_string(),
+		PaymentID: "".to_string(),
+		StartDate: "".to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 1,
+		ChangeIndicatorURL: "".to_string(),
+		Version: "".to_string()
+	};
+
+	for (Key, Value) in URI.query_pairs() {
+		match Key.as_ref() {
+			"tx_payment_id" => Request.PaymentID = Value.into_owned(),
+			"tx_amount" => Request.Amount = Value.into_owned(),
+			"tx_description" => Request.CustomLabel = Value.into_owned(),
+			_ => {}
+		}
+	}
+
+	return Ok(Request);
+}
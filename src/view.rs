@@ -0,0 +1,78 @@
+//! A borrowed view of a decoded request, for scanning many codes without allocating every field.
+use std::borrow::Cow;
+use serde::Deserialize;
+use crate::{CheckPayloadVersion, DecompressBody, DeserializeAmountCow, MoneroRequest, MoneroRequestError, SplitEnvelope};
+
+
+
+/// Same fields as [`MoneroRequest`], but borrowed from the buffer the payload was decompressed into. Made by
+/// [`DecodeRef`].
+///
+/// String fields are borrowed where the JSON holds them verbatim. A string containing an escape (e.g. a `\"` in the
+/// label) or a numeric amount needs unescaping or formatting, so that one field is owned instead; hence `Cow` rather
+/// than `&str`. [`Version`](MoneroRequestRef::Version) borrows from the encoded string.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MoneroRequestRef<'a> {
+	#[serde(rename = "amount", borrow, deserialize_with = "DeserializeAmountCow")]
+	pub Amount: Cow<'a, str>,
+	#[serde(rename = "change_indicator_url", borrow)]
+	pub ChangeIndicatorURL: Cow<'a, str>,
+	#[serde(rename = "currency", borrow)]
+	pub Currency: Cow<'a, str>,
+	#[serde(rename = "custom_label", borrow)]
+	pub CustomLabel: Cow<'a, str>,
+	#[serde(rename = "days_per_billing_cycle")]
+	pub DaysPerBillingCycle: u8,
+	#[serde(rename = "number_of_payments")]
+	pub NumberOfPayments: u8,
+	#[serde(rename = "payment_id", borrow)]
+	pub PaymentID: Cow<'a, str>,
+	#[serde(rename = "sellers_wallet", borrow)]
+	pub SellersWallet: Cow<'a, str>,
+	#[serde(rename = "start_date", borrow)]
+	pub StartDate: Cow<'a, str>,
+	#[serde(rename = "version", borrow, default)]
+	pub Version: Cow<'a, str>
+}
+
+impl MoneroRequestRef<'_> {
+	/// Copies the view into an owned [`MoneroRequest`].
+	pub fn IntoOwned(self) -> MoneroRequest {
+		MoneroRequest {
+			Amount: self.Amount.into_owned(),
+			ChangeIndicatorURL: self.ChangeIndicatorURL.into_owned(),
+			Currency: self.Currency.into_owned(),
+			CustomLabel: self.CustomLabel.into_owned(),
+			DaysPerBillingCycle: self.DaysPerBillingCycle,
+			NumberOfPayments: self.NumberOfPayments,
+			PaymentID: self.PaymentID.into_owned(),
+			SellersWallet: self.SellersWallet.into_owned(),
+			StartDate: self.StartDate.into_owned(),
+			Version: self.Version.into_owned()
+		}
+	}
+}
+
+
+/// Same as [`DecodePaymentRequest`](crate::DecodePaymentRequest), but returns a view that borrows from `Buffer`
+/// rather than an owned request.
+///
+/// The payload is decompressed into `Buffer`, replacing whatever it held, and the view's fields point into it, as does
+/// [`Version`](MoneroRequestRef::Version) into `Request`. So the view can't outlive either, and `Buffer` can't be
+/// reused until the view is dropped. Decompressing still allocates, but only once per code rather than once per field.
+/// Prefer [`DecodePaymentRequest`](crate::DecodePaymentRequest) unless allocation shows up in profiles.
+pub fn DecodeRef<'a>(Request: &'a str, Buffer: &'a mut String) -> Result<MoneroRequestRef<'a>, MoneroRequestError> {
+	let (Version, Body) = SplitEnvelope(Request)?;
+
+	*Buffer = DecompressBody(Body)?;
+
+	let mut Request: MoneroRequestRef = match serde_json::from_str(Buffer) {
+		Ok(r) => r,
+		Err(e) => return Err(MoneroRequestError::SerdeError(e))
+	};
+
+	CheckPayloadVersion(&Request.Version, Version)?;
+	if Request.Version.is_empty() { Request.Version = Cow::Borrowed(Version); }
+
+	return Ok(Request);
+}
@@ -0,0 +1,68 @@
+#![allow(non_snake_case)]
+use proptest::prelude::*;
+use MoneroRequest_Rust::{self, MoneroRequest};
+
+// Checksum valid mainnet addresses, one of each type
+const WALLETS: [&str; 4] = [
+	"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S",
+	"45sJmdSX4DN3tgWwDmspAW1Jd6fD1ax6UJtdBe4boS6YWAiRgm887LcH6neriqYCch2USZuubsuH8Vf4Ub3c36BuQEfW4K6",
+	"86BF71nnNLo1jG3c4GzZrpBpzsEvpSpcvQ3yaD74aS9iG4mcR6HteGTFhpiGxwWzkT2tBi26SMyH8bNhBQMeWXqj2XcRY68",
+	"4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx"
+];
+
+/// Valid requests, with every field set so that validation has nothing to fill in.
+fn ValidRequest() -> impl Strategy<Value = MoneroRequest> {
+	(
+		"\\PC{1,40}",
+		proptest::sample::select(&WALLETS[..]),
+		prop_oneof![
+			("USD", "[0-9]{1,6}(\\.[0-9]{1,2})?"),
+			("XMR", "[0-9]{1,6}(\\.[0-9]{1,12})?"),
+			("XMR", "")
+		],
+		"[0-9a-fA-F]{16}",
+		0i64..4_102_444_800_000,
+		1u8..,
+		any::<u8>(),
+		prop_oneof![Just("".to_string()), "https://[a-z]{1,10}\\.com/[a-z]{0,10}"]
+	).prop_map(|(Label, Wallet, (Currency, Amount), PaymentID, Millis, Days, Payments, URL)| MoneroRequest {
+		Amount,
+		ChangeIndicatorURL: URL,
+		Currency: Currency.to_string(),
+		CustomLabel: Label,
+		DaysPerBillingCycle: Days,
+		NumberOfPayments: Payments,
+		// An integrated address's payment id is fixed by the address
+		PaymentID: MoneroRequest_Rust::IntegratedPaymentID(Wallet).unwrap().unwrap_or(PaymentID),
+		SellersWallet: Wallet.to_string(),
+		StartDate: chrono::DateTime::from_timestamp_millis(Millis).unwrap().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+		Version: "1".to_string()
+	})
+}
+
+proptest! {
+	#[test]
+	fn Test_RoundTrip(Request in ValidRequest()) {
+		let Code = MoneroRequest_Rust::EncodePaymentRequest(Request.clone()).unwrap();
+		let Output = MoneroRequest_Rust::DecodePaymentRequest(Code).unwrap();
+
+		prop_assert_eq!(Output, Request.Normalized().unwrap());
+	}
+
+	#[test]
+	fn Test_NormalizedIdempotent(Request in ValidRequest()) {
+		let Once = Request.Normalized().unwrap();
+
+		prop_assert_eq!(Once.clone().Normalized().unwrap(), Once);
+	}
+
+	#[test]
+	fn Test_DecodeArbitraryString(Input in "\\PC*") {
+		let _ = MoneroRequest_Rust::DecodePaymentRequest(Input);
+	}
+
+	#[test]
+	fn Test_DecodeArbitraryBody(Body in "[A-Za-z0-9+/=]*") {
+		let _ = MoneroRequest_Rust::DecodePaymentRequest(format!("monero-request:1:{Body}"));
+	}
+}
@@ -1,5 +1,32 @@
 #![allow(non_snake_case)]
-use MoneroRequest_Rust::{self, MoneroRequest};
+use std::io::Write;
+use base64::Engine;
+use MoneroRequest_Rust::{self, AddressType, MoneroRequest, Network};
+
+/// A request with only the required fields set. Everything else is left for validation to default.
+fn MinimalRequest() -> MoneroRequest {
+	MoneroRequest {
+		CustomLabel: "".to_string(),
+		SellersWallet: "4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S".to_string(),
+		Currency: "XMR".to_string(),
+		Amount: "1".to_string(),
+		PaymentID: "".to_string(),
+		StartDate: "".to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 1,
+		ChangeIndicatorURL: "".to_string(),
+		Version: "".to_string()
+	}
+}
+
+/// Wraps a raw JSON payload in the gzip, base64, and `monero-request:` envelope without any validation.
+fn EncodeRawPayload(Version: &str, Json: &str) -> String {
+	let mut GZip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+	GZip.write_all(Json.as_bytes()).unwrap();
+	let Body = base64::engine::general_purpose::STANDARD.encode(GZip.finish().unwrap());
+
+	format!("monero-request:{Version}:{Body}")
+}
 
 #[test]
 fn Test_GenRandomPaymentID() {
@@ -9,6 +36,25 @@ fn Test_GenRandomPaymentID() {
 	assert!(Output.chars().all(|c| matches!(c, '0'..='9' | 'a'..='f')), "Invalid hex character found.");
 }
 
+#[test]
+fn Test_GenRandomPaymentIDWithCase() {
+	let Output = MoneroRequest_Rust::GenRandomPaymentIDWithCase(MoneroRequest_Rust::HexCase::Upper);
+
+	assert_eq!(Output.len(), 16);
+	assert!(Output.chars().all(|c| matches!(c, '0'..='9' | 'A'..='F')), "Invalid hex character found.");
+	assert!(MoneroRequest_Rust::PaymentID::try_from(Output.as_str()).is_ok());
+}
+
+#[test]
+fn Test_GenRandomPaymentIDOfLength() {
+	assert_eq!(MoneroRequest_Rust::GenRandomPaymentIDOfLength(8).len(), 16);
+	assert_eq!(MoneroRequest_Rust::GenRandomPaymentIDOfLength(32).len(), 64);
+
+	// Only the standard length validates
+	let Input = MoneroRequest { PaymentID: MoneroRequest_Rust::GenRandomPaymentIDOfLength(32), ..MinimalRequest() };
+	assert!(Input.Normalized().is_err());
+}
+
 #[test]
 fn Test_EncodePaymentRequest() {
 	let Input = MoneroRequest {
@@ -24,11 +70,1535 @@ fn Test_EncodePaymentRequest() {
 		Version: "1".to_string()
 	};
 
+	let Output = MoneroRequest_Rust::EncodePaymentRequest(Input.clone());
+
+	assert!(Output.is_ok(), "Error encoding request: {:?}", Output.unwrap_err());
+
+	let Output = Output.unwrap();
+
+	assert!(Output.starts_with("monero-request:1:"));
+
+	// The PaymentID is generated, so the exact code differs each run; everything else decodes back as given
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(Output).unwrap();
+	assert_eq!(MoneroRequest { PaymentID: "".to_string(), ..Output }, Input);
+}
+
+#[test]
+fn Test_EncodeSubaddress() {
+	let mut Input = MinimalRequest();
+	Input.SellersWallet = "86BF71nnNLo1jG3c4GzZrpBpzsEvpSpcvQ3yaD74aS9iG4mcR6HteGTFhpiGxwWzkT2tBi26SMyH8bNhBQMeWXqj2XcRY68".to_string();
+
+	let Output = MoneroRequest_Rust::EncodePaymentRequest(Input);
+
+	assert!(Output.is_ok(), "Error encoding request: {:?}", Output.unwrap_err());
+}
+
+#[test]
+fn Test_ParseWalletAddress() {
+	let Output = MoneroRequest_Rust::ParseWalletAddress("4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S");
+	assert_eq!(Output.unwrap(), (Network::Mainnet, AddressType::Standard));
+
+	// Stagenet subaddress. This one is synthetic: arbitrary keys under the stagenet subaddress prefix with a valid
+	// checksum, rather than an address from a real wallet.
+	let Output = MoneroRequest_Rust::ParseWalletAddress("73cVaNP7CBhR6CYoXYcqXsfUikHZ7pvg11jktG6LKwL35cV95ofBYnYhNRidVvByQw2rrhhJQHxyT2x9wtgtxikw6jMwDEB");
+	assert_eq!(Output.unwrap(), (Network::Stagenet, AddressType::Subaddress));
+
+	// Last character altered, checksum no longer matches
+	let Output = MoneroRequest_Rust::ParseWalletAddress("4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2T");
+	assert!(Output.is_err());
+}
+
+#[test]
+fn Test_IntegratedAddressLeadingChar() {
+	let Integrated = "4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx";
+	assert!(MoneroRequest_Rust::ValidateWalletAddress(Integrated).is_ok());
+
+	let Output = MoneroRequest_Rust::ValidateWalletAddress(&Integrated.replacen('4', "8", 1));
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid wallet address. Integrated addresses start with 4."))));
+}
+
+#[test]
+fn Test_Normalized() {
+	let Input = MinimalRequest();
+
+	let Output = Input.Normalized();
+
+	assert!(Output.is_ok(), "Error normalizing request: {:?}", Output.unwrap_err());
+
+	let Output = Output.unwrap();
+
+	assert_eq!(Output.CustomLabel, "Monero Payment Request");
+	assert_eq!(Output.PaymentID.len(), 16);
+	assert!(!Output.StartDate.is_empty());
+	assert_eq!(Output.Version, "1");
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn Test_Cbor() {
+	let Input = MinimalRequest().Normalized().unwrap();
+
+	let Output = MoneroRequest_Rust::ToCbor(Input.clone());
+	assert!(Output.is_ok(), "Error serializing request: {:?}", Output.unwrap_err());
+
+	// Every field round-trips, the version included
+	let Output = MoneroRequest_Rust::FromCbor(&Output.unwrap()).unwrap();
+	assert_eq!(Output.Version, "1");
+	assert_eq!(Output, Input);
+}
+
+#[test]
+fn Test_EncodeWithVersion() {
+	let Output = MoneroRequest_Rust::EncodeWithVersion(MinimalRequest(), "1");
+	assert!(Output.unwrap().starts_with("monero-request:1:"));
+
+	let Output = MoneroRequest_Rust::EncodeWithVersion(MinimalRequest(), "2");
+	assert!(Output.is_err());
+}
+
+#[test]
+fn Test_DecodeVersionMismatch() {
+	let Json = r#"{"custom_label":"A label","sellers_wallet":"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S","currency":"XMR","amount":"1","payment_id":"0123456789abcdef","start_date":"2023-04-26T13:45:33.123Z","days_per_billing_cycle":30,"number_of_payments":1,"change_indicator_url":"","version":"2"}"#;
+
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", Json));
+
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Version mismatch between envelope and payload"))));
+
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &Json.replace(r#""version":"2""#, r#""version":"1""#)));
+
+	assert!(Output.is_ok(), "Error decoding request: {:?}", Output.unwrap_err());
+
+	// A payload without a version takes the envelope's
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &Json.replace(r#","version":"2""#, "")));
+
+	assert_eq!(Output.unwrap().Version, "1");
+}
+
+#[test]
+fn Test_EncodePayload() {
+	let Output = MoneroRequest_Rust::EncodePayload(&MinimalRequest());
+
+	assert!(Output.is_ok(), "Error encoding payload: {:?}", Output.unwrap_err());
+
+	let Output = Output.unwrap();
+	assert!(!Output.starts_with("monero-request:"));
+
+	let Output = MoneroRequest_Rust::DecodePayload(&Output).unwrap();
+	assert_eq!(Output.SellersWallet, MinimalRequest().SellersWallet);
+
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(format!("monero-request:1:{}", MoneroRequest_Rust::EncodePayload(&MinimalRequest()).unwrap()));
+	assert!(Output.is_ok(), "Error decoding request: {:?}", Output.unwrap_err());
+}
+
+#[test]
+fn Test_Newtypes() {
+	let Wallet = MoneroRequest_Rust::WalletAddress::try_from("4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S").unwrap();
+	assert_eq!(Wallet.to_string(), MinimalRequest().SellersWallet);
+	assert_eq!(Wallet.len(), 95);
+
+	let PaymentID = MoneroRequest_Rust::PaymentID::try_from("0123456789abcdef").unwrap();
+	assert_eq!(PaymentID.as_ref(), "0123456789abcdef");
+
+	// Stored lowercase, as Normalized stores it
+	let PaymentID = MoneroRequest_Rust::PaymentID::try_from("60B6A010501201F1").unwrap();
+	assert_eq!(PaymentID.as_ref(), "60b6a010501201f1");
+	assert_eq!("60B6A010501201F1".parse::<MoneroRequest_Rust::PaymentID>().unwrap(), PaymentID);
+	assert_eq!(PaymentID.as_ref(), MoneroRequest { PaymentID: "60B6A010501201F1".to_string(), ..MinimalRequest() }.Normalized().unwrap().PaymentID);
+
+	assert!(MoneroRequest_Rust::PaymentID::try_from("0123").is_err());
+	assert!(MoneroRequest_Rust::WalletAddress::try_from("4At3X5").is_err());
+}
+
+#[test]
+fn Test_AmountDecimalPlaces() {
+	assert_eq!(MoneroRequest_Rust::MaxDecimalPlaces("USD"), Some(2));
+	assert_eq!(MoneroRequest_Rust::MaxDecimalPlaces("XMR"), Some(12));
+	assert_eq!(MoneroRequest_Rust::MaxDecimalPlaces("EUR"), None);
+
+	let mut Input = MinimalRequest();
+	Input.Currency = "USD".to_string();
+	Input.Amount = "123.456".to_string();
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Too many decimal places for USD"))));
+
+	let mut Input = MinimalRequest();
+	Input.Amount = "0.123456789012".to_string();
+	assert!(Input.Normalized().is_ok());
+
+	let mut Input = MinimalRequest();
+	Input.Amount = "0.1234567890123".to_string();
+	assert!(Input.Normalized().is_err());
+}
+
+#[test]
+fn Test_AmbiguousAmountFormat() {
+	// US convention: commas group thousands, a period is the decimal point
+	for Amount in ["1,234", "1,234.56", "1.234", "12,345,678"] {
+		assert!(MoneroRequest { Amount: Amount.to_string(), ..MinimalRequest() }.Normalized().is_ok(), "{Amount}");
+	}
+
+	for Amount in ["1,23", "1,2345", "1.234,56", "1.234.567", ",123", "1234,567"] {
+		let Output = MoneroRequest { Amount: Amount.to_string(), ..MinimalRequest() }.Normalized();
+		assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Ambiguous amount format"))), "{Amount}");
+	}
+}
+
+#[test]
+fn Test_InvalidAmount() {
+	for Amount in ["abc1", "1e5", "1.5e3", "--1", "-1", "1 XMR", "١٢٣", "1.", ".5", " 1"] {
+		let Output = MoneroRequest { Amount: Amount.to_string(), ..MinimalRequest() }.Normalized();
+		assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid Amount."))), "{Amount}");
+	}
+}
+
+#[test]
+fn Test_RequireDomainChangeIndicatorURL() {
+	let Options = MoneroRequest_Rust::ValidationOptions { RequireDomainChangeIndicatorURL: true, ..Default::default() };
+
+	let mut Input = MinimalRequest();
+	Input.ChangeIndicatorURL = "https://127.0.0.1/changes".to_string();
+	assert!(Input.clone().Normalized().is_ok());
+	assert!(matches!(Input.NormalizedWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("ChangeIndicatorURL must use a domain name"))));
+
+	let mut Input = MinimalRequest();
+	Input.ChangeIndicatorURL = "https://[::1]/changes".to_string();
+	assert!(Input.NormalizedWith(&Options).is_err());
+
+	let mut Input = MinimalRequest();
+	Input.ChangeIndicatorURL = "https://example.com/changes".to_string();
+	assert!(MoneroRequest_Rust::EncodeWithOptions(Input, &Options).is_ok());
+}
+
+#[test]
+fn Test_ChangeIndicatorURLScheme() {
+	for URL in ["javascript://example.com/%0aalert(1)", "data://example.com/text", "http://example.com/changes", "ftp://example.com/changes"] {
+		let Input = MoneroRequest { ChangeIndicatorURL: URL.to_string(), ..MinimalRequest() };
+		assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("ChangeIndicatorURL must use https"))), "{URL}");
+	}
+
+	// data: URLs without an authority can't be a base, so they fail before the scheme is checked
+	let Input = MoneroRequest { ChangeIndicatorURL: "data:text/html,<script>alert(1)</script>".to_string(), ..MinimalRequest() };
+	assert!(Input.Normalized().is_err());
+}
+
+#[cfg(feature = "test-networks")]
+#[test]
+fn Test_AllowHttpChangeIndicatorURL() {
+	let Options = MoneroRequest_Rust::ValidationOptions { AllowHttpChangeIndicatorURL: true, ..Default::default() };
+
+	let Input = MoneroRequest { ChangeIndicatorURL: "http://localhost:8080/changes".to_string(), ..MinimalRequest() };
+	assert!(Input.NormalizedWith(&Options).is_ok());
+
+	let Input = MoneroRequest { ChangeIndicatorURL: "javascript://example.com/%0aalert(1)".to_string(), ..MinimalRequest() };
+	assert!(Input.NormalizedWith(&Options).is_err());
+}
+
+#[test]
+fn Test_MoneroURI() {
+	let mut Input = MinimalRequest();
+	Input.CustomLabel = "Tea & Coffee = 2? Yes".to_string();
+	Input.PaymentID = "0123456789abcdef".to_string();
+
+	let Output = MoneroRequest_Rust::ToMoneroURI(&Input).unwrap();
+	assert!(Output.starts_with(&format!("monero:{}?", Input.SellersWallet)));
+	assert!(!Output.contains(" "));
+
+	let Output = MoneroRequest_Rust::ParseMoneroURI(&Output).unwrap();
+	assert_eq!(Output.CustomLabel, "Tea & Coffee = 2? Yes");
+	assert_eq!(Output.SellersWallet, Input.SellersWallet);
+	assert_eq!(Output.PaymentID, Input.PaymentID);
+	assert_eq!(Output.Amount, Input.Amount);
+}
+
+#[test]
+fn Test_DecodeNumericAmount() {
+	let Json = r#"{"custom_label":"A label","sellers_wallet":"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S","currency":"XMR","amount":AMOUNT,"payment_id":"0123456789abcdef","start_date":"2023-04-26T13:45:33.123Z","days_per_billing_cycle":30,"number_of_payments":1,"change_indicator_url":""}"#;
+
+	// Numbers keep their digits exactly, however many an f64 could hold
+	for (Amount, Expected) in [("1.5", "1.5"), ("2", "2"), (r#""0.25""#, "0.25"), ("0.123456789012", "0.123456789012"), ("12345678.123456789", "12345678.123456789"), ("18446744073709551616", "18446744073709551616")] {
+		let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &Json.replace("AMOUNT", Amount))).unwrap();
+		assert_eq!(Output.Amount, Expected);
+	}
+
+	assert!(MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &Json.replace("AMOUNT", "true"))).is_err());
+
+	// The same through the borrowed view
+	let Code = EncodeRawPayload("1", &Json.replace("AMOUNT", "12345678.123456789"));
+	let mut Buffer = String::new();
+	assert_eq!(MoneroRequest_Rust::DecodeRef(&Code, &mut Buffer).unwrap().Amount, "12345678.123456789");
+}
+
+#[test]
+fn Test_DecodePaymentRequestStrict() {
+	// Wallet with its last character altered, so the checksum fails
+	let Json = r#"{"custom_label":"A label","sellers_wallet":"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2T","currency":"XMR","amount":"1","payment_id":"0123456789abcdef","start_date":"2023-04-26T13:45:33.123Z","days_per_billing_cycle":30,"number_of_payments":1,"change_indicator_url":"","version":"1"}"#;
+
+	assert!(MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", Json)).is_ok());
+	assert!(matches!(MoneroRequest_Rust::DecodePaymentRequestStrict(EncodeRawPayload("1", Json)), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid wallet address checksum."))));
+
+	let Output = MoneroRequest_Rust::DecodePaymentRequestStrict(MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap());
+	assert!(Output.is_ok(), "Error decoding request: {:?}", Output.unwrap_err());
+}
+
+#[test]
+fn Test_OpenAmount() {
+	let mut Input = MinimalRequest();
+	Input.Amount = "".to_string();
+	assert!(Input.IsOpenAmount());
+	assert!(!MinimalRequest().IsOpenAmount());
+
 	let Output = MoneroRequest_Rust::EncodePaymentRequest(Input);
+	assert!(Output.is_ok(), "Error encoding request: {:?}", Output.unwrap_err());
 
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(Output.unwrap()).unwrap();
+	assert_eq!(Output.Amount, "");
+}
+
+#[test]
+fn Test_AmountRequiredForSubscriptions() {
+	let Open = |Currency: &str, NumberOfPayments: u8| MoneroRequest { Currency: Currency.to_string(), Amount: "".to_string(), NumberOfPayments, ..MinimalRequest() };
+
+	// One-off open amounts and open XMR subscriptions are fine
+	assert!(Open("USD", 1).Normalized().is_ok());
+	assert!(Open("XMR", 1).Normalized().is_ok());
+	assert!(Open("XMR", 12).Normalized().is_ok());
+	assert!(Open("XMR", 0).Normalized().is_ok());
+
+	// USD subscriptions need an amount, whether fixed length or indefinite
+	for NumberOfPayments in [12, 0] {
+		assert!(matches!(Open("USD", NumberOfPayments).Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::MissingFields(r)) if r == vec![MoneroRequest_Rust::Field::Amount]));
+	}
+
+	// The rule is configurable per currency
+	let Options = MoneroRequest_Rust::ValidationOptions { AmountRequiredForSubscriptions: vec![MoneroRequest_Rust::Currency::XMR], ..Default::default() };
+	assert!(Open("USD", 12).NormalizedWith(&Options).is_ok());
+	assert!(matches!(Open("XMR", 12).NormalizedWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::MissingFields(r)) if r == vec![MoneroRequest_Rust::Field::Amount]));
+}
+
+#[test]
+fn Test_CurrencyIsoCode() {
+	use MoneroRequest_Rust::Currency;
+
+	assert_eq!(Currency::FromIsoCode("USD").unwrap(), Currency::USD);
+	assert_eq!(Currency::FromIsoCode("XMR").unwrap(), Currency::XMR);
+	// Any case, as Normalized accepts
+	assert_eq!(Currency::FromIsoCode("usd").unwrap(), Currency::USD);
+	assert_eq!("Xmr".parse::<Currency>().unwrap(), Currency::XMR);
+	assert_eq!(Currency::XMR.IsoCode(), "XMR");
+	assert_eq!(String::from(Currency::USD), "USD");
+	assert!(matches!(Currency::FromIsoCode("EUR"), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Unsupported currency code."))));
+}
+
+#[test]
+fn Test_CurrencyCaseInsensitive() {
+	for (Input, Expected) in [("usd", "USD"), ("Xmr", "XMR"), ("USD", "USD")] {
+		let Input = MoneroRequest { Currency: Input.to_string(), ..MinimalRequest() };
+		assert_eq!(Input.Normalized().unwrap().Currency, Expected);
+	}
+
+	assert!(MoneroRequest { Currency: "eur".to_string(), ..MinimalRequest() }.Normalized().is_err());
+}
+
+#[test]
+fn Test_RequiresNetwork() {
+	assert_eq!(MinimalRequest().RequiresNetwork().unwrap(), Network::Mainnet);
+
+	let mut Input = MinimalRequest();
+	Input.SellersWallet = "566VBMbst6MASSNmb683hfTQBw9Axekk5aaKf7teu8HeDdTsT5uJo99HnGBfbfoWLU2Ror42Rpind8hhTjrAmxVaHMW2i2i".to_string();
+	assert_eq!(Input.RequiresNetwork().unwrap(), Network::Stagenet);
+
+	let mut Input = MinimalRequest();
+	Input.SellersWallet = "".to_string();
+	assert!(Input.RequiresNetwork().is_err());
+}
+
+#[test]
+fn Test_UsesIntegratedAddress() {
+	assert!(!MinimalRequest().UsesIntegratedAddress().unwrap());
+
+	let mut Input = MinimalRequest();
+	Input.SellersWallet = "4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx".to_string();
+	assert!(Input.UsesIntegratedAddress().unwrap());
+
+	Input.SellersWallet = "4At3X5".to_string();
+	assert!(Input.UsesIntegratedAddress().is_err());
+}
+
+#[test]
+fn Test_PaymentIDCase() {
+	let mut Input = MinimalRequest();
+	Input.PaymentID = "60b6a010501201f1".to_string();
+	assert_eq!(Input.Normalized().unwrap().PaymentID, "60b6a010501201f1");
+
+	let mut Input = MinimalRequest();
+	Input.PaymentID = "60B6A010501201F1".to_string();
+	assert_eq!(Input.Normalized().unwrap().PaymentID, "60b6a010501201f1");
+
+	let mut Input = MinimalRequest();
+	Input.PaymentID = "60b6A010501201F1".to_string();
+	assert_eq!(Input.Normalized().unwrap().PaymentID, "60b6a010501201f1");
+
+	// Encoding stores the canonical form too
+	let mut Input = MinimalRequest();
+	Input.PaymentID = "60B6A010501201F1".to_string();
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(MoneroRequest_Rust::EncodePaymentRequest(Input).unwrap()).unwrap();
+	assert_eq!(Output.PaymentID, "60b6a010501201f1");
+
+	let mut Input = MinimalRequest();
+	Input.PaymentID = "60B6A010501201G1".to_string();
+	assert!(Input.Normalized().is_err());
+}
+
+#[cfg(feature = "minified")]
+#[test]
+fn Test_EncodeMinified() {
+	let Output = MoneroRequest_Rust::EncodeMinified(MinimalRequest());
 	assert!(Output.is_ok(), "Error encoding request: {:?}", Output.unwrap_err());
 
 	let Output = Output.unwrap();
+	assert!(Output.len() < MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap().len());
+
+	let Output = MoneroRequest_Rust::DecodeMinified(&Output).unwrap();
+	assert_eq!(Output.CustomLabel, MoneroRequest_Rust::DEFAULT_LABEL);
+	assert_eq!(Output.ChangeIndicatorURL, "");
+	assert_eq!(Output.Version, "1");
+}
+
+#[cfg(feature = "clap")]
+#[test]
+fn Test_ClapParsers() {
+	let Command = || clap::Command::new("pay")
+		.arg(clap::Arg::new("wallet").value_parser(MoneroRequest_Rust::WalletAddressParser()))
+		.arg(clap::Arg::new("currency").value_parser(MoneroRequest_Rust::CurrencyParser()));
+
+	let Output = Command().try_get_matches_from(["pay", &MinimalRequest().SellersWallet, "XMR"]).unwrap();
+	assert_eq!(Output.get_one::<MoneroRequest_Rust::WalletAddress>("wallet").unwrap().as_ref(), MinimalRequest().SellersWallet);
+	assert_eq!(*Output.get_one::<MoneroRequest_Rust::Currency>("currency").unwrap(), MoneroRequest_Rust::Currency::XMR);
+
+	let Output = Command().try_get_matches_from(["pay", &MinimalRequest().SellersWallet, "usd"]).unwrap();
+	assert_eq!(*Output.get_one::<MoneroRequest_Rust::Currency>("currency").unwrap(), MoneroRequest_Rust::Currency::USD);
+
+	let Output = Command().try_get_matches_from(["pay", "4At3X5", "XMR"]).unwrap_err();
+	assert!(Output.to_string().contains("Incorrect seller wallet address length."));
+}
+
+#[cfg(feature = "deflate-raw")]
+#[test]
+fn Test_EncodeDeflateRaw() {
+	// Both encodings must see the same payload, so nothing can be left for validation to fill in with the current time
+	let Input = MoneroRequest { PaymentID: "0123456789abcdef".to_string(), StartDate: "2024-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() };
+	let Output = MoneroRequest_Rust::EncodeDeflateRaw(Input.clone()).unwrap();
+	assert_eq!(Output.len() + 24, MoneroRequest_Rust::EncodePaymentRequest(Input.clone()).unwrap().len());
+
+	let Output = MoneroRequest_Rust::DecodeDeflateRaw(&Output).unwrap();
+	assert_eq!(Output.PaymentID, Input.PaymentID);
+	assert_eq!(Output.Version, "1");
+
+	// Standard decoders can't read it
+	assert!(MoneroRequest_Rust::DecodePaymentRequest(MoneroRequest_Rust::EncodeDeflateRaw(Input).unwrap()).is_err());
+}
+
+#[cfg(feature = "schemars")]
+#[test]
+fn Test_JsonSchema() {
+	let Output = MoneroRequest_Rust::JsonSchema();
+	let Properties = Output["properties"].as_object().unwrap();
+
+	assert!(Properties.contains_key("sellers_wallet"));
+	assert!(Properties.contains_key("change_indicator_url"));
+	assert!(!Properties.contains_key("SellersWallet"));
+	assert_eq!(Output["properties"]["days_per_billing_cycle"]["type"], "integer");
+}
+
+#[test]
+fn Test_Diff() {
+	let Old = MinimalRequest();
+	let mut New = MinimalRequest();
+	New.Amount = "2".to_string();
+	New.NumberOfPayments = 12;
+
+	let Output = Old.Diff(&New);
+	assert_eq!(Output, vec![
+		MoneroRequest_Rust::FieldChange { Field: MoneroRequest_Rust::Field::Amount, Old: "1".to_string(), New: "2".to_string() },
+		MoneroRequest_Rust::FieldChange { Field: MoneroRequest_Rust::Field::NumberOfPayments, Old: "1".to_string(), New: "12".to_string() }
+	]);
+
+	assert!(Old.Diff(&MinimalRequest()).is_empty());
+}
+
+#[test]
+fn Test_ExtractRequests() {
+	let First = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let mut Second = MinimalRequest();
+	Second.Amount = "2".to_string();
+	let Second = MoneroRequest_Rust::EncodePaymentRequest(Second).unwrap();
+
+	let Text = format!("Hi,\n\nPlease pay {First}. Or the larger one:\n\t{Second}\nThis one is broken: monero-request:1:AAAA and that's it.");
+	let Output = MoneroRequest_Rust::ExtractRequests(&Text);
+
+	assert_eq!(Output.len(), 2);
+	assert_eq!(Output[0].Amount, "1");
+	assert_eq!(Output[1].Amount, "2");
+
+	assert!(MoneroRequest_Rust::ExtractRequests("Nothing to see here").is_empty());
+}
+
+#[test]
+fn Test_RequestCodec() {
+	use MoneroRequest_Rust::RequestCodec;
+
+	// Anything generic over RequestCodec works with the standard format
+	fn RoundTrip(Codec: &impl RequestCodec, Request: &MoneroRequest) -> MoneroRequest {
+		Codec.Decode(&Codec.Encode(Request).unwrap()).unwrap()
+	}
+
+	let Codec = MoneroRequest_Rust::StandardCodec::default();
+	let Output = RoundTrip(&Codec, &MinimalRequest());
+	assert_eq!(Output.Amount, "1");
+
+	// The free functions and the codec produce interchangeable codes
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	assert_eq!(Codec.Decode(&Code).unwrap().PaymentID, MoneroRequest_Rust::DecodePaymentRequest(Code).unwrap().PaymentID);
+}
+
+#[test]
+fn Test_MaxXMRAmount() {
+	let mut Request = MinimalRequest();
+	Request.Amount = "100000000000000000000".to_string();
+	assert!(matches!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("XMR amount exceeds plausible maximum"))));
+
+	// Exponent forms aren't amounts at all
+	Request.Amount = "1e20".to_string();
+	assert!(matches!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid Amount."))));
+
+	// Right at the ceiling
+	Request.Amount = "100,000,000.000000000000".to_string();
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()).is_ok());
+	Request.Amount = "100000000.0000000001".to_string();
+	assert!(matches!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("XMR amount exceeds plausible maximum"))));
+	Request.Amount = "100000000.000000000001".to_string();
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()).is_err());
+	Request.Amount = "1".repeat(40);
+	assert!(matches!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("XMR amount exceeds plausible maximum"))));
+
+	// The ceiling is tunable
+	Request.Amount = "18,400,000".to_string();
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Request.clone()).is_ok());
+	let Options = MoneroRequest_Rust::ValidationOptions { MaxXMRAmount: 1_000, ..Default::default() };
+	assert!(MoneroRequest_Rust::EncodeWithOptions(Request.clone(), &Options).is_err());
+
+	// USD has no ceiling
+	Request.Currency = "USD".to_string();
+	Request.Amount = "100000000000000000000".to_string();
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Request).is_ok());
+}
+
+#[test]
+fn Test_DisplayAmount() {
+	let mut Request = MinimalRequest();
+	Request.Currency = "USD".to_string();
+	Request.Amount = "123.45".to_string();
+	assert_eq!(Request.DisplayAmount(), "$123.45");
+
+	Request.Amount = "1234567.5".to_string();
+	assert_eq!(Request.DisplayAmount(), "$1,234,567.5");
+
+	Request.Currency = "XMR".to_string();
+	Request.Amount = "1,000".to_string();
+	assert_eq!(Request.DisplayAmount(), "1,000 XMR");
+
+	Request.Amount = "0.000000000001".to_string();
+	assert_eq!(Request.DisplayAmount(), "0.000000000001 XMR");
+
+	// Amounts that aren't plain decimals are shown as entered, not grouped
+	for Amount in ["€1234", "-123", "1e5", "١٢٣٤"] {
+		Request.Amount = Amount.to_string();
+		assert_eq!(Request.DisplayAmount(), format!("{Amount} XMR"));
+	}
+
+	Request.Amount = "".to_string();
+	assert_eq!(Request.DisplayAmount(), "");
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn Test_CtEq() {
+	use zeroize::Zeroize;
+
+	let mut Request = MinimalRequest();
+	Request.PaymentID = "0123456789abcdef".to_string();
+	assert!(Request.CtEq(&Request.clone()));
+
+	let mut Other = Request.clone();
+	Other.PaymentID = "0123456789abcdee".to_string();
+	assert!(!Request.CtEq(&Other));
+
+	let mut Other = Request.clone();
+	Other.Amount = "2".to_string();
+	assert!(!Request.CtEq(&Other));
+
+	Request.zeroize();
+	assert!(Request.SellersWallet.is_empty() && Request.PaymentID.is_empty());
+}
+
+#[test]
+fn Test_IsValidEncoding() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	assert!(MoneroRequest_Rust::IsValidEncoding(&Code));
+
+	// Only the encoding is checked, not the request
+	assert!(MoneroRequest_Rust::IsValidEncoding(&EncodeRawPayload("1", r#"{"amount":"1"}"#)));
+
+	assert!(!MoneroRequest_Rust::IsValidEncoding(&EncodeRawPayload("1", "[1, 2]")));
+	assert!(!MoneroRequest_Rust::IsValidEncoding(&EncodeRawPayload("1", "not json")));
+	assert!(!MoneroRequest_Rust::IsValidEncoding(&EncodeRawPayload("2", "{}")));
+	assert!(!MoneroRequest_Rust::IsValidEncoding("monero-request:1:!!!!"));
+	assert!(!MoneroRequest_Rust::IsValidEncoding(&Code.replacen("monero-request", "monero-invoice", 1)));
+}
+
+#[test]
+fn Test_DecodeLenient() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Legacy = Code.replacen("monero-request:1:", "monero-request:", 1);
+
+	// Strict decoding rejects the two-part form
+	assert!(MoneroRequest_Rust::DecodePaymentRequest(Legacy.clone()).is_err());
+
+	let Output = MoneroRequest_Rust::DecodeLenient(&Legacy).unwrap();
+	assert_eq!(Output.Amount, "1");
+	assert_eq!(Output.Version, "1");
+
+	// Versioned codes still decode as usual
+	assert!(MoneroRequest_Rust::DecodeLenient(&Code).is_ok());
+	assert!(MoneroRequest_Rust::DecodeLenient("monero-request").is_err());
+}
+
+#[test]
+fn Test_Lint() {
+	assert!(MinimalRequest().Lint().is_empty());
+
+	let mut Request = MinimalRequest();
+	Request.CustomLabel = "Order 1234567 for jane@example.com".to_string();
+	Request.PaymentID = "0000000000000000".to_string();
+	Request.Currency = "USD".to_string();
+	Request.Amount = "10".to_string();
+	assert_eq!(Request.Lint(), vec![
+		MoneroRequest_Rust::Lint::LabelContainsEmail,
+		MoneroRequest_Rust::Lint::LabelContainsLongNumber,
+		MoneroRequest_Rust::Lint::PaymentIDAllZeroes,
+		MoneroRequest_Rust::Lint::AmountHasNoDecimalsForUSD
+	]);
+
+	// Lints don't block encoding
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Request).is_ok());
+}
+
+#[test]
+fn Test_GoldenReferenceCode() {
+	// The example code from the README, produced by the reference implementation
+	let Reference = "monero-request:1:H4sIAAAAAAAC/y1QXVPCMBD8K0yegWmbftC+FQRndHAUiiIvmTS50o5pgkmqto7/3RR9ut3b29u5+0a0VZ20KENBNE9TNEWspvIMpJG8YdQqTTotnDwqndYgWe/YYX9zbRirWiJoCePIQV4R8MlWSdBq8kj7FqSd7OC9A2Odg9PekAtoUjZCNPJMWM8EoAx7UyS7tnSKqsjlz2dQ5k/RPyENdxF+sAjjsEpwxfwkwcytNCAEaEM+qavjIWFu8THSH8/9pVDVue3gITXpk9UD30G07GCjzVt+avxkqV7LeuiNGga13Szj4UUW9/x2Fedf67xcryM2bHa4duiuNG1Yr+AY7MdIS7UlnFoY/+YFeOZ7syAuvCjDi8xP5zEOT+jnF8JbIrJdAQAA";
+
+	let Input = MoneroRequest {
+		Amount: "25.99".to_string(),
+		ChangeIndicatorURL: "".to_string(),
+		Currency: "USD".to_string(),
+		CustomLabel: "Unlabeled Monero Payment Request".to_string(),
+		DaysPerBillingCycle: 30,
+		NumberOfPayments: 1,
+		PaymentID: "128464f73fc1773c".to_string(),
+		SellersWallet: "4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S".to_string(),
+		StartDate: "2023-10-26T05:38:19.634Z".to_string(),
+		Version: "1".to_string()
+	};
+
+	assert_eq!(MoneroRequest_Rust::EncodePaymentRequest(Input.clone()).unwrap(), Reference);
+
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(Reference.to_string()).unwrap();
+	assert!(Output.Diff(&Input).is_empty());
+}
+
+#[test]
+fn Test_AmountAtomic() {
+	let mut Request = MinimalRequest();
+	assert_eq!(Request.AmountAtomic().unwrap(), 1_000_000_000_000);
+
+	Request.Amount = "0.000000000001".to_string();
+	assert_eq!(Request.AmountAtomic().unwrap(), 1);
+
+	Request.Amount = "1,234.5".to_string();
+	assert_eq!(Request.AmountAtomic().unwrap(), 1_234_500_000_000_000);
+
+	Request.Amount = "18446744.073709551616".to_string();
+	assert!(matches!(Request.AmountAtomic(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("XMR amount overflows atomic units."))));
+
+	Request.Amount = "0.0000000000001".to_string();
+	assert!(Request.AmountAtomic().is_err());
+
+	Request.Currency = "USD".to_string();
+	Request.Amount = "1".to_string();
+	assert!(Request.AmountAtomic().is_err());
+}
+
+#[test]
+fn Test_IntegratedAddressPaymentID() {
+	let Integrated = "4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx";
+	assert_eq!(MoneroRequest_Rust::IntegratedPaymentID(Integrated).unwrap().as_deref(), Some("123456789abcdef0"));
+	assert_eq!(MoneroRequest_Rust::IntegratedPaymentID(&MinimalRequest().SellersWallet).unwrap(), None);
 
-	assert!(Output == "Some string");
-}
\ No newline at end of file
+	let mut Request = MinimalRequest();
+	Request.SellersWallet = Integrated.to_string();
+	assert_eq!(Request.clone().Normalized().unwrap().PaymentID, "123456789abcdef0");
+
+	Request.PaymentID = "123456789ABCDEF0".to_string();
+	assert!(Request.clone().Normalized().is_ok());
+
+	Request.PaymentID = "0000000000000000".to_string();
+	assert!(Request.Normalized().is_err());
+}
+
+#[test]
+fn Test_PaymentIDMode() {
+	let Options = MoneroRequest_Rust::ValidationOptions { PaymentIDMode: MoneroRequest_Rust::PaymentIDMode::None, ..Default::default() };
+	let Output = MinimalRequest().NormalizedWith(&Options).unwrap();
+	assert_eq!(Output.PaymentID, MoneroRequest_Rust::NO_PAYMENT_ID);
+
+	// A given PaymentID is kept
+	let Input = MoneroRequest { PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+	assert_eq!(Input.NormalizedWith(&Options).unwrap().PaymentID, "0123456789abcdef");
+
+	let Options = MoneroRequest_Rust::ValidationOptions { PaymentIDMode: MoneroRequest_Rust::PaymentIDMode::Explicit("FEDCBA9876543210".to_string()), ..Default::default() };
+	assert_eq!(MinimalRequest().NormalizedWith(&Options).unwrap().PaymentID, "fedcba9876543210");
+	let Options = MoneroRequest_Rust::ValidationOptions { PaymentIDMode: MoneroRequest_Rust::PaymentIDMode::Explicit("xyz".to_string()), ..Default::default() };
+	assert!(MinimalRequest().NormalizedWith(&Options).is_err());
+
+	// Generating stays the default
+	assert_ne!(MinimalRequest().Normalized().unwrap().PaymentID, MoneroRequest_Rust::NO_PAYMENT_ID);
+}
+
+#[test]
+fn Test_NormalizedIdempotent() {
+	for StartDate in ["", "2023-10-26T05:38:19.634Z", "2023-10-26T07:38:19.634567+02:00", "2023-10-26T05:38:19Z"] {
+		let Input = MoneroRequest { StartDate: StartDate.to_string(), ..MinimalRequest() };
+
+		let Once = Input.Normalized().unwrap();
+		let Twice = Once.clone().Normalized().unwrap();
+		assert_eq!(Once, Twice);
+	}
+}
+
+#[cfg(feature = "test-networks")]
+#[test]
+fn Test_AllowTestNetworks() {
+	let Options = MoneroRequest_Rust::ValidationOptions { AllowTestNetworks: true, ..Default::default() };
+
+	for Wallet in [
+		"9tpzWHTwdmX8nS3Ro1p3UYba4RHewzoXK57pmEMRhkDZbgRKh3uR7ifDF2zUBUHcpnikEZ2XzVqAx5C7WZXbAL2GV1vSCnY",
+		"566VBMbst6MASSNmb683hfTQBw9Axekk5aaKf7teu8HeDdTsT5uJo99HnGBfbfoWLU2Ror42Rpind8hhTjrAmxVaHMW2i2i",
+		"73cVaNP7CBhR6CYoXYcqXsfUikHZ7pvg11jktG6LKwL35cV95ofBYnYhNRidVvByQw2rrhhJQHxyT2x9wtgtxikw6jMwDEB"
+	] {
+		let Input = MoneroRequest { SellersWallet: Wallet.to_string(), ..MinimalRequest() };
+		assert!(MoneroRequest_Rust::EncodeWithOptions(Input.clone(), &Options).is_ok());
+
+		// Default validation stays mainnet only
+		assert!(MoneroRequest_Rust::EncodePaymentRequest(Input).is_err());
+	}
+
+	// Checksums are still checked
+	let Input = MoneroRequest { SellersWallet: "9tpzWHTwdmX8nS3Ro1p3UYba4RHewzoXK57pmEMRhkDZbgRKh3uR7ifDF2zUBUHcpnikEZ2XzVqAx5C7WZXbAL2GV1vSCnZ".to_string(), ..MinimalRequest() };
+	assert!(MoneroRequest_Rust::EncodeWithOptions(Input, &Options).is_err());
+}
+
+#[test]
+fn Test_EnvelopeInfo() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	assert_eq!(MoneroRequest_Rust::EnvelopeInfo(&Code).unwrap(), ("monero-request".to_string(), 1));
+
+	// Only the header is looked at
+	assert_eq!(MoneroRequest_Rust::EnvelopeInfo("monero-request:12:not base64").unwrap().1, 12);
+
+	assert!(MoneroRequest_Rust::EnvelopeInfo("monero-request:1:").is_err());
+	assert!(MoneroRequest_Rust::EnvelopeInfo("monero-request:x:AAAA").is_err());
+	assert!(MoneroRequest_Rust::EnvelopeInfo("monero:1:AAAA").is_err());
+	assert!(MoneroRequest_Rust::EnvelopeInfo("monero-request:99999999999:AAAA").is_err());
+}
+
+#[test]
+fn Test_RemainingPayments() {
+	let Request = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), DaysPerBillingCycle: 30, NumberOfPayments: 3, ..MinimalRequest() };
+	let Date = |S: &str| S.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+
+	assert_eq!(Request.PaymentSchedule().unwrap(), vec![Date("2024-01-01T00:00:00Z"), Date("2024-01-31T00:00:00Z"), Date("2024-03-01T00:00:00Z")]);
+
+	assert_eq!(Request.RemainingPayments(Date("2023-12-01T00:00:00Z")).unwrap().len(), 3);
+	assert_eq!(Request.RemainingPayments(Date("2024-01-31T00:00:00Z")).unwrap(), vec![Date("2024-01-31T00:00:00Z"), Date("2024-03-01T00:00:00Z")]);
+	assert!(Request.RemainingPayments(Date("2024-06-01T00:00:00Z")).unwrap().is_empty());
+
+	let Indefinite = MoneroRequest { NumberOfPayments: 0, ..Request.clone() };
+	assert!(Indefinite.RemainingPayments(Date("2024-01-01T00:00:00Z")).is_err());
+}
+
+#[test]
+fn Test_EmptyCurrency() {
+	let Input = MoneroRequest { Currency: "".to_string(), ..MinimalRequest() };
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::MissingFields(r)) if r == vec![MoneroRequest_Rust::Field::Currency]));
+
+	let Input = MoneroRequest { Currency: "EUR".to_string(), ..MinimalRequest() };
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid Currency."))));
+}
+
+#[test]
+fn Test_DecodeLines() {
+	let First = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Second = MoneroRequest_Rust::EncodePaymentRequest(MoneroRequest { Amount: "2".to_string(), ..MinimalRequest() }).unwrap();
+	let Input = format!("{First}\n\n  \nnot a code\n{Second}\r\n");
+
+	let Output: Vec<_> = MoneroRequest_Rust::DecodeLines(std::io::Cursor::new(Input)).collect();
+	assert_eq!(Output.len(), 3);
+	assert_eq!(Output[0].as_ref().unwrap().Amount, "1");
+	assert!(Output[1].is_err());
+	assert_eq!(Output[2].as_ref().unwrap().Amount, "2");
+}
+
+#[test]
+fn Test_MeasureSize() {
+	let Input = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+	let Output = MoneroRequest_Rust::MeasureSize(&Input).unwrap();
+
+	assert_eq!(Output.TotalWithPrefix, MoneroRequest_Rust::EncodePaymentRequest(Input).unwrap().len());
+	assert_eq!(Output.TotalWithPrefix, Output.Base64Chars + "monero-request:1:".len());
+	assert!(Output.GZipBytes < Output.JsonBytes);
+}
+
+#[test]
+fn Test_EncodedLen() {
+	let Input = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+
+	assert_eq!(MoneroRequest_Rust::EncodedLen(&Input).unwrap(), MoneroRequest_Rust::EncodePaymentRequest(Input).unwrap().len());
+	assert!(MoneroRequest_Rust::EncodedLen(&MoneroRequest { Currency: "".to_string(), ..MinimalRequest() }).is_err());
+}
+
+#[test]
+fn Test_DefaultLabelOption() {
+	let Options = MoneroRequest_Rust::ValidationOptions { DefaultLabel: "ACME Store Payment".to_string(), ..Default::default() };
+	assert_eq!(MinimalRequest().NormalizedWith(&Options).unwrap().CustomLabel, "ACME Store Payment");
+
+	// An explicit label wins, and the built-in default applies otherwise
+	let Input = MoneroRequest { CustomLabel: "Coffee".to_string(), ..MinimalRequest() };
+	assert_eq!(Input.NormalizedWith(&Options).unwrap().CustomLabel, "Coffee");
+	assert_eq!(MinimalRequest().Normalized().unwrap().CustomLabel, MoneroRequest_Rust::DEFAULT_LABEL);
+}
+
+#[test]
+fn Test_NextPaymentDate() {
+	let Request = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), DaysPerBillingCycle: 30, NumberOfPayments: 3, ..MinimalRequest() };
+	let Date = |S: &str| S.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+
+	assert_eq!(Request.NextPaymentDate(Date("2023-12-01T00:00:00Z")).unwrap(), Some(Date("2024-01-01T00:00:00Z")));
+	// Strictly after, so a payment due right now isn't next
+	assert_eq!(Request.NextPaymentDate(Date("2024-01-01T00:00:00Z")).unwrap(), Some(Date("2024-01-31T00:00:00Z")));
+	assert_eq!(Request.NextPaymentDate(Date("2024-02-15T00:00:00Z")).unwrap(), Some(Date("2024-03-01T00:00:00Z")));
+	assert_eq!(Request.NextPaymentDate(Date("2024-03-01T00:00:00Z")).unwrap(), None);
+
+	let Indefinite = MoneroRequest { NumberOfPayments: 0, ..Request.clone() };
+	assert_eq!(Indefinite.NextPaymentDate(Date("2030-01-01T00:00:00Z")).unwrap(), Some(Date("2030-01-29T00:00:00Z")));
+
+	// The next date would be past the last representable one
+	let Daily = MoneroRequest { DaysPerBillingCycle: 1, ..Indefinite };
+	assert_eq!(Daily.NextPaymentDate(chrono::DateTime::<chrono::Utc>::MAX_UTC).unwrap(), None);
+	let Monthly = MoneroRequest { NumberOfPayments: 255, ..Request };
+	assert_eq!(Monthly.Status(chrono::DateTime::<chrono::Utc>::MAX_UTC).unwrap(), MoneroRequest_Rust::RequestStatus::Completed);
+}
+
+#[test]
+fn Test_WalletAddressLookalikes() {
+	// The valid address with a lookalike substituted for one character
+	let Wallet = MinimalRequest().SellersWallet;
+	for (From, To) in [("o", "0"), ("o", "O"), ("i", "I"), ("i", "l")] {
+		let Input = Wallet.replacen(From, To, 1);
+		assert_ne!(Input, Wallet);
+		assert!(matches!(MoneroRequest_Rust::ValidateWalletAddress(&Input), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid base58 character in wallet address"))));
+	}
+}
+
+#[test]
+fn Test_MaskAddress() {
+	let Wallet = MinimalRequest().SellersWallet;
+	assert_eq!(MoneroRequest_Rust::MaskAddress(&Wallet), "4At3X5…hCeX2S");
+	assert_eq!(MoneroRequest_Rust::MaskAddressWith(&Wallet, 4), "4At3…eX2S");
+	assert_eq!(MoneroRequest_Rust::MaskAddressWith("4At3X5", 4), "4At3X5");
+}
+
+#[test]
+fn Test_DecodeSizeLimits() {
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(format!("monero-request:1:{}", "A".repeat(MoneroRequest_Rust::MAX_ENCODED_LENGTH)));
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Encoded request is too long."))));
+
+	// The legacy and bare payload entry points have the same cap
+	let TooLong = |Output| matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Encoded request is too long.")));
+	let Oversized = "A".repeat(MoneroRequest_Rust::MAX_ENCODED_LENGTH);
+	assert!(TooLong(MoneroRequest_Rust::DecodeLenient(&format!("monero-request:{Oversized}"))));
+	assert!(TooLong(MoneroRequest_Rust::DecodePayload(&format!("{Oversized}A"))));
+
+	// A payload of spaces gzips down to almost nothing but inflates past the limit
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &" ".repeat(MoneroRequest_Rust::MAX_PAYLOAD_BYTES + 1)));
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Decompressed payload is too large."))));
+}
+
+#[cfg(feature = "cache")]
+#[test]
+fn Test_DecodeCache() {
+	use MoneroRequest_Rust::RequestCodec;
+
+	let Cache = MoneroRequest_Rust::DecodeCache::new(std::num::NonZeroUsize::new(1).unwrap());
+	let First = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Second = MoneroRequest_Rust::EncodePaymentRequest(MoneroRequest { Amount: "2".to_string(), ..MinimalRequest() }).unwrap();
+
+	assert_eq!(Cache.Decode(&First).unwrap(), MoneroRequest_Rust::DecodePaymentRequest(First.clone()).unwrap());
+	assert_eq!(Cache.Decode(&First).unwrap().Amount, "1");
+	assert_eq!(Cache.Len(), 1);
+
+	// Failures aren't cached, and the second code evicts the first
+	assert!(Cache.Decode("monero-request:1:invalid").is_err());
+	assert_eq!(Cache.Len(), 1);
+	assert_eq!(Cache.Decode(&Second).unwrap().Amount, "2");
+	assert_eq!(Cache.Len(), 1);
+
+	Cache.Clear();
+	assert_eq!(Cache.Len(), 0);
+}
+
+#[test]
+fn Test_ToOwnedError() {
+	let Error = MoneroRequest { Currency: "EUR".to_string(), ..MinimalRequest() }.Normalized().unwrap_err();
+	assert_eq!(Error.ToOwnedError(), MoneroRequest_Rust::OwnedError::InvalidInput("Invalid Currency."));
+
+	// Wrapped errors keep their message, and the copy can go to another thread
+	let Error = MoneroRequest_Rust::DecodePaymentRequest("monero-request:1:!!!!".to_string()).unwrap_err();
+	let Owned = Error.ToOwnedError();
+	assert!(matches!(Owned, MoneroRequest_Rust::OwnedError::Other(_)));
+	assert_eq!(std::thread::spawn({ let Owned = Owned.clone(); move || Owned.to_string() }).join().unwrap(), Error.to_string());
+}
+
+#[test]
+fn Test_Summary() {
+	let Input = MoneroRequest { Currency: "USD".to_string(), Amount: "123.45".to_string(), NumberOfPayments: 12, StartDate: "2023-04-26T13:45:33.123Z".to_string(), ..MinimalRequest() };
+	assert_eq!(Input.Summary(), "Monero Payment Request: $123.45/month for 12 months to 4At3X5…hCeX2S, starting 2023-04-26.");
+
+	let Input = MoneroRequest { CustomLabel: "Donation".to_string(), Amount: "".to_string(), ..MinimalRequest() };
+	assert_eq!(Input.Summary(), "Donation: Any amount to 4At3X5…hCeX2S.");
+
+	let Input = MoneroRequest { DaysPerBillingCycle: 14, NumberOfPayments: 0, ..MinimalRequest() };
+	assert_eq!(Input.Summary(), "Monero Payment Request: 1 XMR every 14 days, ongoing to 4At3X5…hCeX2S.");
+}
+
+#[test]
+fn Test_DecodeRef() {
+	let Input = MoneroRequest { CustomLabel: "A \"quoted\" label".to_string(), ..MinimalRequest() };
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(Input).unwrap();
+
+	let mut Buffer = String::new();
+	let Output = MoneroRequest_Rust::DecodeRef(&Code, &mut Buffer).unwrap();
+	assert!(matches!(Output.SellersWallet, std::borrow::Cow::Borrowed(_)));
+	assert!(matches!(Output.CustomLabel, std::borrow::Cow::Owned(_)));
+	assert_eq!(Output.Version, "1");
+	assert_eq!(Output.IntoOwned(), MoneroRequest_Rust::DecodePaymentRequest(Code.clone()).unwrap());
+
+	assert!(MoneroRequest_Rust::DecodeRef("monero-request:1:invalid", &mut Buffer).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn Test_EncodePar() {
+	let Input = vec![
+		MoneroRequest { PaymentID: "0123456789abcdef".to_string(), StartDate: "2024-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() },
+		MoneroRequest { Currency: "".to_string(), ..MinimalRequest() }
+	];
+	let Output = MoneroRequest_Rust::EncodePar(&Input);
+
+	assert_eq!(Output.len(), 2);
+	assert_eq!(Output[0].as_ref().unwrap(), &MoneroRequest_Rust::EncodePaymentRequest(Input[0].clone()).unwrap());
+	assert!(Output[1].is_err());
+}
+
+#[test]
+fn Test_WithNewPaymentID() {
+	let Template = MoneroRequest { PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+
+	let First = Template.clone().WithNewPaymentID();
+	let Second = Template.clone().WithNewPaymentID();
+	assert_ne!(First.PaymentID, Template.PaymentID);
+	assert_ne!(First.PaymentID, Second.PaymentID);
+	assert!(First.Normalized().is_ok());
+
+	let Seeded = Template.clone().WithNewPaymentIDSeeded(42);
+	assert_eq!(Seeded.PaymentID, Template.clone().WithNewPaymentIDSeeded(42).PaymentID);
+	assert_ne!(Seeded.PaymentID, Template.WithNewPaymentIDSeeded(43).PaymentID);
+	assert!(Seeded.Normalized().is_ok());
+}
+
+#[test]
+fn Test_MaxStartDateDaysAhead() {
+	let Options = MoneroRequest_Rust::ValidationOptions { MaxStartDateDaysAhead: Some(3650), ..Default::default() };
+
+	let Input = MoneroRequest { StartDate: "3000-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() };
+	assert!(Input.clone().Normalized().is_ok());
+	assert!(matches!(Input.NormalizedWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("StartDate too far in the future"))));
+
+	let Input = MoneroRequest { StartDate: (chrono::Utc::now() + chrono::Duration::days(365)).to_rfc3339(), ..MinimalRequest() };
+	assert!(Input.NormalizedWith(&Options).is_ok());
+	assert!(MinimalRequest().NormalizedWith(&Options).is_ok());
+}
+
+#[test]
+fn Test_EncodeBytes() {
+	let Input = MoneroRequest { PaymentID: "0123456789abcdef".to_string(), StartDate: "2024-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() };
+	let Output = MoneroRequest_Rust::EncodeBytes(&Input).unwrap();
+	assert_eq!(Output, MoneroRequest_Rust::EncodePaymentRequest(Input.clone()).unwrap().into_bytes());
+
+	assert_eq!(MoneroRequest_Rust::DecodeBytes(&Output).unwrap(), Input.Normalized().unwrap());
+	assert!(matches!(MoneroRequest_Rust::DecodeBytes(&[0xff, 0xfe]), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Encoded request is not valid UTF-8."))));
+}
+
+#[test]
+fn Test_Check() {
+	// Defaults that would be filled in don't fail the check, and nothing is changed
+	let Input = MinimalRequest();
+	assert!(Input.Check().is_ok());
+	assert_eq!(Input, MinimalRequest());
+
+	let Input = MoneroRequest { Currency: "".to_string(), ..MinimalRequest() };
+	assert!(matches!(Input.Check(), Err(MoneroRequest_Rust::MoneroRequestError::MissingFields(r)) if r == vec![MoneroRequest_Rust::Field::Currency]));
+
+	let Options = MoneroRequest_Rust::ValidationOptions { RequireDomainChangeIndicatorURL: true, ..Default::default() };
+	let Input = MoneroRequest { ChangeIndicatorURL: "https://127.0.0.1/changes".to_string(), ..MinimalRequest() };
+	assert!(Input.Check().is_ok());
+	assert!(Input.CheckWith(&Options).is_err());
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn Test_FixedPaymentID() {
+	// Pinned so that snapshots relying on it can't silently change
+	assert_eq!(MoneroRequest_Rust::FixedPaymentID(0), "e220a8397b1dcdaf");
+	assert_eq!(MoneroRequest_Rust::FixedPaymentID(0), MoneroRequest_Rust::FixedPaymentID(0));
+	assert_ne!(MoneroRequest_Rust::FixedPaymentID(0), MoneroRequest_Rust::FixedPaymentID(1));
+
+	let Input = MoneroRequest { PaymentID: MoneroRequest_Rust::FixedPaymentID(7), ..MinimalRequest() };
+	assert!(Input.Normalized().is_ok());
+}
+
+#[test]
+fn Test_EmptyVersusAbsentFields() {
+	let Json = r#"{"custom_label":"A label","sellers_wallet":"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S","currency":"XMR","amount":"1","payment_id":"0123456789abcdef","start_date":"2023-04-26T13:45:33.123Z","days_per_billing_cycle":30,"number_of_payments":1,"change_indicator_url":""}"#;
+
+	// An empty field round trips as empty
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", Json)).unwrap();
+	assert_eq!(Output.ChangeIndicatorURL, "");
+	let Output = MoneroRequest_Rust::DecodePaymentRequest(MoneroRequest_Rust::EncodePaymentRequest(Output).unwrap()).unwrap();
+	assert_eq!(Output.ChangeIndicatorURL, "");
+
+	// The standard requires every field, so leaving one out is an error rather than read as empty
+	let Json = Json.replace(r#","change_indicator_url":"""#, "");
+	assert!(matches!(MoneroRequest_Rust::DecodePaymentRequest(EncodeRawPayload("1", &Json)), Err(MoneroRequest_Rust::MoneroRequestError::SerdeError(_))));
+}
+
+#[test]
+fn Test_ByteLengthLimits() {
+	// 64 emoji are 256 bytes, one more is over, even though it's far fewer than 256 characters
+	let Input = MoneroRequest { CustomLabel: "🦀".repeat(64), ..MinimalRequest() };
+	assert!(Input.Normalized().is_ok());
+	let Input = MoneroRequest { CustomLabel: "🦀".repeat(65), ..MinimalRequest() };
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("CustomLabel exceeds 256 bytes"))));
+	assert_eq!(MoneroRequest_Rust::MAX_LABEL_BYTES, 256);
+
+	let Input = MoneroRequest { ChangeIndicatorURL: format!("https://example.com/{}", "a".repeat(MoneroRequest_Rust::MAX_URL_BYTES)), ..MinimalRequest() };
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("ChangeIndicatorURL exceeds 512 bytes"))));
+	assert_eq!(MoneroRequest_Rust::MAX_URL_BYTES, 512);
+}
+
+#[test]
+fn Test_ReferenceVectors() {
+	// Produced with the reference implementation's encoding (Python's json.dumps with sorted keys, gzip.compress with an
+	// mtime of 0). The first is the README example; the rest cover XMR, open amounts, other address types, the \u
+	// escapes json.dumps writes for non-ASCII labels, and other encoders' gzip settings: the fastest level, no
+	// compression at all, and a smaller window.
+	let Vectors: Vec<serde_json::Value> = serde_json::from_str(include_str!("reference_vectors.json")).unwrap();
+	assert!(!Vectors.is_empty());
+
+	for Vector in Vectors {
+		let Expected = MoneroRequest { Version: "1".to_string(), ..serde_json::from_value(Vector["request"].clone()).unwrap() };
+		let Output = MoneroRequest_Rust::DecodePaymentRequest(Vector["code"].as_str().unwrap().to_string()).unwrap();
+
+		assert!(Output.Diff(&Expected).is_empty(), "{}: {:?}", Vector["description"], Output.Diff(&Expected));
+
+		// Only vectors compressed at gzip's best level, with no non-ASCII for json.dumps to escape, encode to the same bytes
+		if Vector["byte_identical"].as_bool().unwrap() {
+			assert_eq!(MoneroRequest_Rust::EncodePaymentRequest(Expected).unwrap(), Vector["code"].as_str().unwrap(), "{}", Vector["description"]);
+		}
+	}
+}
+
+#[cfg(feature = "test-utils")]
+#[test]
+fn Test_CheckReferenceVector() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Expected = MoneroRequest_Rust::DecodePaymentRequest(Code.clone()).unwrap();
+
+	assert!(MoneroRequest_Rust::CheckReferenceVector(&Code, &Expected).unwrap().is_empty());
+	let Output = MoneroRequest_Rust::CheckReferenceVector(&Code, &MoneroRequest { Amount: "2".to_string(), ..Expected }).unwrap();
+	assert_eq!(Output.len(), 1);
+	assert_eq!(Output[0].Field, MoneroRequest_Rust::Field::Amount);
+}
+
+#[test]
+fn Test_CompactParams() {
+	let Input = MinimalRequest().Normalized().unwrap();
+	let Output = Input.ToCompactParams();
+	assert_eq!(Output.keys().map(String::as_str).collect::<Vec<_>>(), ["a", "c", "p", "s", "w"]);
+	assert_eq!(MoneroRequest::FromCompactParams(&Output).unwrap(), Input);
+
+	// Fields that differ from their default are kept even when empty
+	let Input = MoneroRequest { CustomLabel: "".to_string(), NumberOfPayments: 0, ..MinimalRequest() };
+	let Output = Input.ToCompactParams();
+	assert_eq!(Output["l"], "");
+	assert_eq!(MoneroRequest::FromCompactParams(&Output).unwrap(), Input);
+
+	let mut Output = Input.ToCompactParams();
+	Output.insert("x".to_string(), "".to_string());
+	assert!(MoneroRequest::FromCompactParams(&Output).is_err());
+	let Output = std::collections::BTreeMap::from([("d".to_string(), "300".to_string())]);
+	assert!(MoneroRequest::FromCompactParams(&Output).is_err());
+}
+
+#[test]
+fn Test_PaymentRequest() {
+	let Request = MoneroRequest_Rust::PaymentRequest::from(MoneroRequest { NumberOfPayments: 3, StartDate: "2024-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() }).Validate().unwrap();
+
+	assert_eq!(MoneroRequest_Rust::DecodePaymentRequest(Request.Encode().unwrap()).unwrap(), Request.Request);
+	assert_eq!(Request.ToURI().unwrap(), MoneroRequest_Rust::ToMoneroURI(&Request.Request).unwrap());
+	assert_eq!(Request.Schedule().unwrap().len(), 3);
+	#[cfg(feature = "qr")]
+	assert!(Request.ToQR().is_ok());
+
+	assert!(MoneroRequest_Rust::PaymentRequest::from(MoneroRequest { Currency: "".to_string(), ..MinimalRequest() }).Validate().is_err());
+}
+
+#[test]
+fn Test_DecodeAnyCompressionLevel() {
+	let Json = r#"{"custom_label":"A label","sellers_wallet":"4At3X5rvVypTofgmueN9s9QtrzdRe5BueFrskAZi17BoYbhzysozzoMFB6zWnTKdGC6AxEAbEE5czFR3hbEEJbsm4hCeX2S","currency":"XMR","amount":"1","payment_id":"0123456789abcdef","start_date":"2023-04-26T13:45:33.123Z","days_per_billing_cycle":30,"number_of_payments":1,"change_indicator_url":""}"#;
+
+	for Level in 0..=9 {
+		let mut GZip = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(Level));
+		GZip.write_all(Json.as_bytes()).unwrap();
+		let Code = format!("monero-request:1:{}", base64::engine::general_purpose::STANDARD.encode(GZip.finish().unwrap()));
+
+		assert_eq!(MoneroRequest_Rust::DecodePaymentRequest(Code).unwrap().CustomLabel, "A label", "Level {Level}");
+	}
+}
+
+#[test]
+fn Test_MatchesPayment() {
+	let Request = MoneroRequest { Amount: "1.5".to_string(), PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+	let Payment = MoneroRequest_Rust::ReceivedPayment { Address: Request.SellersWallet.clone(), PaymentID: "0123456789ABCDEF".to_string(), AmountAtomic: 1_500_000_000_000 };
+
+	assert!(Request.MatchesPayment(&Payment));
+	assert!(Request.MatchesPayment(&MoneroRequest_Rust::ReceivedPayment { AmountAtomic: 2_000_000_000_000, ..Payment.clone() }));
+	assert!(!Request.MatchesPayment(&MoneroRequest_Rust::ReceivedPayment { AmountAtomic: 1_499_999_999_999, ..Payment.clone() }));
+	assert!(!Request.MatchesPayment(&MoneroRequest_Rust::ReceivedPayment { PaymentID: "fedcba9876543210".to_string(), ..Payment.clone() }));
+	assert!(!Request.MatchesPayment(&MoneroRequest_Rust::ReceivedPayment { Address: "86BF71nnNLo1jG3c4GzZrpBpzsEvpSpcvQ3yaD74aS9iG4mcR6HteGTFhpiGxwWzkT2tBi26SMyH8bNhBQMeWXqj2XcRY68".to_string(), ..Payment.clone() }));
+
+	// Open amounts take anything, other currencies can't be compared
+	assert!(MoneroRequest { Amount: "".to_string(), ..Request.clone() }.MatchesPayment(&MoneroRequest_Rust::ReceivedPayment { AmountAtomic: 1, ..Payment.clone() }));
+	assert!(!MoneroRequest { Currency: "USD".to_string(), ..Request }.MatchesPayment(&Payment));
+
+	// An integrated address's payment id is the embedded one
+	let Wallet = "4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx";
+	let Request = MoneroRequest { SellersWallet: Wallet.to_string(), ..MinimalRequest() };
+	let Payment = MoneroRequest_Rust::ReceivedPayment { Address: Wallet.to_string(), PaymentID: "123456789abcdef0".to_string(), AmountAtomic: 1_000_000_000_000 };
+	assert!(Request.MatchesPayment(&Payment));
+}
+
+#[test]
+fn Test_ApproxXMR() {
+	let Decimal = |S: &str| S.parse::<MoneroRequest_Rust::Decimal>().unwrap();
+	let Input = MoneroRequest { Currency: "USD".to_string(), Amount: "25.00".to_string(), ..MinimalRequest() };
+	assert_eq!(Input.ApproxXMR(Decimal("150")).unwrap(), Decimal("0.166666666667"));
+	assert_eq!(MoneroRequest { Amount: "1,500".to_string(), ..Input.clone() }.ApproxXMR(Decimal("150")).unwrap(), Decimal("10"));
+	assert_eq!(MoneroRequest { Amount: "0.30".to_string(), ..Input.clone() }.ApproxXMR(Decimal("0.1")).unwrap(), Decimal("3"));
+	// Half up at the 12th place
+	assert_eq!(MoneroRequest { Amount: "0.01".to_string(), ..Input.clone() }.ApproxXMR(Decimal("16")).unwrap(), Decimal("0.000625000000"));
+	assert_eq!(MoneroRequest { Amount: "0.01".to_string(), ..Input.clone() }.ApproxXMR(Decimal("16000000000")).unwrap(), Decimal("0.000000000001"));
+
+	// Tiny rates are valid, but the result can outgrow a Decimal
+	assert_eq!(Input.ApproxXMR(Decimal("0.0000000001")).unwrap(), Decimal("250000000000"));
+	let TooLarge = MoneroRequest { Amount: "100000000000000000000".to_string(), ..Input.clone() }.ApproxXMR(Decimal("0.0000000001"));
+	assert!(matches!(TooLarge, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Approximate XMR is too large to represent."))));
+
+	// Bad rates are their own error
+	for Rate in ["0", "-150"] {
+		assert!(matches!(Input.ApproxXMR(Decimal(Rate)), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Invalid exchange rate."))));
+	}
+
+	assert!(MoneroRequest { Amount: "1e300".to_string(), ..Input.clone() }.ApproxXMR(Decimal("150")).is_err());
+	assert!(MoneroRequest { Amount: "".to_string(), ..Input }.ApproxXMR(Decimal("150")).is_err());
+	assert!(MinimalRequest().ApproxXMR(Decimal("150")).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn Test_EncodeParUnique() {
+	let Input = vec![
+		MoneroRequest { PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() },
+		MinimalRequest(),
+		MoneroRequest { PaymentID: "0123456789ABCDEF".to_string(), ..MinimalRequest() }
+	];
+	let Output = MoneroRequest_Rust::EncodeParUnique(&Input);
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::DuplicatePaymentID { First: 0, Second: 2, .. })), "{Output:?}");
+
+	// Without the duplicate everything encodes, and NO_PAYMENT_ID may repeat
+	let NoID = MoneroRequest { PaymentID: MoneroRequest_Rust::NO_PAYMENT_ID.to_string(), ..MinimalRequest() };
+	let Output = MoneroRequest_Rust::EncodeParUnique(&[Input[0].clone(), Input[1].clone(), NoID.clone(), NoID]).unwrap();
+	assert_eq!(Output.len(), 4);
+	assert!(Output.iter().all(Result::is_ok));
+}
+
+#[test]
+fn Test_IntoParts() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let (Scheme, Version, Body) = MoneroRequest_Rust::IntoParts(&Code).unwrap();
+	assert_eq!((Scheme.as_str(), Version), ("monero-request", 1));
+	assert_eq!(MoneroRequest_Rust::DecodePayload(&Body).unwrap().SellersWallet, MinimalRequest().SellersWallet);
+
+	// Re-framed with another delimiter and put back together
+	let Framed = format!("{}|{}|{}", Scheme, Version, Body);
+	let Parts: Vec<&str> = Framed.split('|').collect();
+	let Rebuilt = MoneroRequest_Rust::FromParts(Parts[0], Parts[1].parse().unwrap(), Parts[2]).unwrap();
+	assert_eq!(Rebuilt, Code);
+
+	assert!(MoneroRequest_Rust::IntoParts("monero-request:9:AAAA").is_err());
+	assert!(MoneroRequest_Rust::IntoParts("monero:1:AAAA").is_err());
+	assert!(MoneroRequest_Rust::FromParts("monero", 1, &Body).is_err());
+	assert!(MoneroRequest_Rust::FromParts("monero-request", 9, &Body).is_err());
+	assert!(MoneroRequest_Rust::FromParts("monero-request", 1, "").is_err());
+}
+
+#[test]
+fn Test_MissingFields() {
+	// Every empty required field is listed at once, including the amount of a subscription that needs one
+	let Empty = MoneroRequest { SellersWallet: "".to_string(), Currency: "".to_string(), Amount: "".to_string(), StartDate: "".to_string(), NumberOfPayments: 12, ..MinimalRequest() };
+	let Error = MoneroRequest_Rust::EncodePaymentRequest(Empty.clone()).unwrap_err();
+	assert!(matches!(Error, MoneroRequest_Rust::MoneroRequestError::MissingFields(ref r) if *r == vec![MoneroRequest_Rust::Field::SellersWallet, MoneroRequest_Rust::Field::Currency, MoneroRequest_Rust::Field::Amount]));
+	assert_eq!(Error.to_string(), "Missing required fields: SellersWallet, Currency, Amount");
+
+	// One-off requests can leave the amount open
+	let OneOff = MoneroRequest { NumberOfPayments: 1, ..Empty.clone() };
+	assert_eq!(OneOff.Check().unwrap_err().to_string(), "Missing required fields: SellersWallet, Currency");
+
+	let NoWallet = MoneroRequest { SellersWallet: "".to_string(), ..MinimalRequest() };
+	assert_eq!(NoWallet.Check().unwrap_err().to_string(), "Missing required fields: SellersWallet");
+
+	// Not only when the wallet is missing
+	let NoCurrencyOrAmount = MoneroRequest { Currency: "".to_string(), Amount: "".to_string(), NumberOfPayments: 0, ..MinimalRequest() };
+	assert_eq!(NoCurrencyOrAmount.Check().unwrap_err().to_string(), "Missing required fields: Currency, Amount");
+}
+
+#[test]
+fn Test_Status() {
+	use MoneroRequest_Rust::RequestStatus;
+	let Date = |S: &str| S.parse::<chrono::DateTime<chrono::Utc>>().unwrap();
+	let Request = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), DaysPerBillingCycle: 30, NumberOfPayments: 3, ..MinimalRequest() };
+
+	assert_eq!(Request.Status(Date("2023-12-31T23:59:59Z")).unwrap(), RequestStatus::NotStarted);
+	assert_eq!(Request.Status(Date("2024-01-01T00:00:00Z")).unwrap(), RequestStatus::Active);
+	assert_eq!(Request.Status(Date("2024-03-30T23:59:59Z")).unwrap(), RequestStatus::Active);
+	assert_eq!(Request.Status(Date("2024-03-31T00:00:00Z")).unwrap(), RequestStatus::Completed);
+
+	// Without an end date these never complete
+	let OneTime = MoneroRequest { NumberOfPayments: 1, ..Request.clone() };
+	assert_eq!(OneTime.Status(Date("2030-01-01T00:00:00Z")).unwrap(), RequestStatus::Active);
+	let Indefinite = MoneroRequest { NumberOfPayments: 0, ..Request.clone() };
+	assert_eq!(Indefinite.Status(Date("2030-01-01T00:00:00Z")).unwrap(), RequestStatus::Active);
+
+	assert!(MoneroRequest { StartDate: "".to_string(), ..Request }.Status(Date("2024-01-01T00:00:00Z")).is_err());
+}
+
+#[test]
+fn Test_RoundAmount() {
+	let Options = MoneroRequest_Rust::ValidationOptions { RoundAmount: true, ..Default::default() };
+	let Round = |Currency: &str, Amount: &str| MoneroRequest { Currency: Currency.to_string(), Amount: Amount.to_string(), ..MinimalRequest() }.NormalizedWith(&Options).unwrap().Amount;
+
+	// Half up
+	assert_eq!(Round("XMR", "0.123456789012345"), "0.123456789012");
+	assert_eq!(Round("XMR", "0.1234567890125"), "0.123456789013");
+	assert_eq!(Round("USD", "9.994"), "9.99");
+	assert_eq!(Round("USD", "9.995"), "10.00");
+	assert_eq!(Round("USD", "999,999.999"), "1,000,000.00");
+	// Already within the limit, left alone
+	assert_eq!(Round("USD", "9.9"), "9.9");
+
+	// Rejected by default
+	let Input = MoneroRequest { Amount: "0.123456789012345".to_string(), ..MinimalRequest() };
+	assert!(matches!(Input.Normalized(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Too many decimal places for XMR"))));
+}
+
+#[test]
+fn Test_AddressValidator() {
+	#[derive(Debug)]
+	struct Experimental;
+	impl MoneroRequest_Rust::AddressValidator for Experimental {
+		fn Validate(&self, Address: &str) -> Result<(), MoneroRequest_Rust::MoneroRequestError> {
+			if Address.starts_with("xmr1") { Ok(()) } else { Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Not an xmr1 address.")) }
+		}
+	}
+
+	let Options = MoneroRequest_Rust::ValidationOptions { AddressValidator: Some(std::sync::Arc::new(Experimental)), ..Default::default() };
+	let Input = MoneroRequest { SellersWallet: "xmr1qqqqqq".to_string(), ..MinimalRequest() };
+	assert!(Input.CheckWith(&Options).is_ok());
+	assert!(Input.Check().is_err());
+
+	// The custom validator replaces the built-in one entirely
+	assert!(matches!(MinimalRequest().CheckWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Not an xmr1 address."))));
+}
+
+#[test]
+fn Test_FromIntegratedAddress() {
+	let Integrated = "4BQwZtYrppZAWiPFdPhxP3YYsaEfRtGz49vLWx9vC2xpaJp2jzDiCCtSgNzSwnutDWVY12Z8ifWAzijxMi3GGK248NYcKpY7hcHU6Nh8xx";
+	let Output = MoneroRequest::FromIntegratedAddress(Integrated, MoneroRequest_Rust::Currency::USD, "9.99").unwrap();
+	assert_eq!(Some(Output.PaymentID.clone()), MoneroRequest_Rust::IntegratedPaymentID(Integrated).unwrap());
+	assert_eq!((Output.SellersWallet.as_str(), Output.Currency.as_str(), Output.Amount.as_str()), (Integrated, "USD", "9.99"));
+	assert!(MoneroRequest_Rust::EncodePaymentRequest(Output).is_ok());
+
+	let Output = MoneroRequest::FromIntegratedAddress(&MinimalRequest().SellersWallet, MoneroRequest_Rust::Currency::XMR, "1");
+	assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Not an integrated address."))));
+	assert!(MoneroRequest::FromIntegratedAddress("4At3X5", MoneroRequest_Rust::Currency::XMR, "1").is_err());
+}
+
+#[test]
+fn Test_DebugJson() {
+	let Input = MoneroRequest { PaymentID: "0123456789abcdef".to_string(), StartDate: "2024-01-01T00:00:00.000Z".to_string(), ..MinimalRequest() };
+	let Json = MoneroRequest_Rust::DebugJson(&Input).unwrap();
+	assert!(Json.contains("\n  \"payment_id\": \"0123456789abcdef\","));
+
+	// The same payload as the code holds, just indented. The version is in the prefix rather than the payload.
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(Input).unwrap();
+	assert_eq!(MoneroRequest { Version: "1".to_string(), ..serde_json::from_str(&Json).unwrap() }, MoneroRequest_Rust::DecodePaymentRequest(Code).unwrap());
+
+	assert!(MoneroRequest_Rust::DebugJson(&MoneroRequest { Currency: "EUR".to_string(), ..MinimalRequest() }).is_err());
+}
+
+#[test]
+fn Test_BillingTermsCoherence() {
+	let Subscription = MoneroRequest { DaysPerBillingCycle: 0, NumberOfPayments: 12, ..MinimalRequest() };
+	assert!(matches!(Subscription.Check(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("DaysPerBillingCycle cannot be zero for a subscription."))));
+	let Indefinite = MoneroRequest { DaysPerBillingCycle: 0, NumberOfPayments: 0, ..MinimalRequest() };
+	assert!(matches!(Indefinite.Check(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("DaysPerBillingCycle cannot be zero for a subscription."))));
+	let OneTime = MoneroRequest { DaysPerBillingCycle: 0, NumberOfPayments: 1, ..MinimalRequest() };
+	assert!(matches!(OneTime.Check(), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("DaysPerBillingCycle cannot be zero."))));
+
+	// A cycle on a one-time payment is ignored, so it's only a lint
+	let OneTime = MoneroRequest { DaysPerBillingCycle: 7, NumberOfPayments: 1, ..MinimalRequest() };
+	assert!(OneTime.Check().is_ok());
+	assert_eq!(OneTime.Lint(), vec![MoneroRequest_Rust::Lint::BillingCycleIgnored]);
+	assert!(MoneroRequest { NumberOfPayments: 4, ..OneTime }.Lint().is_empty());
+}
+
+#[test]
+fn Test_RedactEncoded() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Redacted = MoneroRequest_Rust::RedactEncoded(&Code);
+	assert!(regex::Regex::new(r"^monero-request:1:[0-9a-f]{16}$").unwrap().is_match(&Redacted));
+	assert!(!Redacted.contains(&Code[18..]));
+
+	// Deterministic, but different codes give different output
+	assert_eq!(MoneroRequest_Rust::RedactEncoded(&Code), Redacted);
+	let Other = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	assert_ne!(MoneroRequest_Rust::RedactEncoded(&Other), Redacted);
+
+	// The first 8 bytes of the SHA-256
+	assert_eq!(MoneroRequest_Rust::RedactEncoded("not a code"), "11d3acdc68944163");
+}
+
+#[test]
+fn Test_Diagnose() {
+	use MoneroRequest_Rust::{Layer, LayerStatus};
+	let FailedAt = |Code: &str| MoneroRequest_Rust::Diagnose(Code).FirstFailure().map(|(Layer, _)| Layer);
+
+	let Report = MoneroRequest_Rust::Diagnose(&MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap());
+	assert!(Report.IsValid());
+	assert_eq!(Report.Layers.len(), 6);
+	assert_eq!(Report.Request.unwrap().SellersWallet, MinimalRequest().SellersWallet);
+
+	assert_eq!(FailedAt("monero:1:AAAA"), Some(Layer::Prefix));
+	assert_eq!(FailedAt("monero-request:7:AAAA"), Some(Layer::Version));
+	assert_eq!(FailedAt("monero-request:1:not base64!"), Some(Layer::Base64));
+	assert_eq!(FailedAt("monero-request:1:AAAA"), Some(Layer::GZip));
+	assert_eq!(FailedAt(&EncodeRawPayload("1", "{}")), Some(Layer::Json));
+
+	// Invalid fields still come back decoded, lints included
+	let Json = serde_json::to_string(&MoneroRequest { Currency: "EUR".to_string(), PaymentID: "0000000000000000".to_string(), ..MinimalRequest() }).unwrap();
+	let Report = MoneroRequest_Rust::Diagnose(&EncodeRawPayload("1", &Json));
+	assert!(matches!(Report.FirstFailure(), Some((Layer::Fields, MoneroRequest_Rust::OwnedError::InvalidInput("Invalid Currency.")))));
+	assert_eq!(Report.Request.unwrap().Currency, "EUR");
+	assert_eq!(Report.Lints, vec![MoneroRequest_Rust::Lint::PaymentIDAllZeroes]);
+
+	// Layers after the failure aren't reached
+	let Report = MoneroRequest_Rust::Diagnose("monero-request:1:AAAA");
+	assert_eq!(Report.Layers[3], (Layer::GZip, LayerStatus::Failed(MoneroRequest_Rust::OwnedError::GZipError("Error decompressing data."))));
+	assert_eq!(Report.Layers[4..], [(Layer::Json, LayerStatus::NotReached), (Layer::Fields, LayerStatus::NotReached)]);
+}
+
+#[test]
+fn Test_AddressPrimitives() {
+	use MoneroRequest_Rust::address;
+	let Wallet = MinimalRequest().SellersWallet;
+
+	let Data = address::DecodeBase58(&Wallet).unwrap();
+	assert_eq!(address::EncodeBase58(&Data), Wallet);
+	// A partial final block
+	assert_eq!(address::DecodeBase58(&address::EncodeBase58(&[1, 2, 3])).unwrap(), vec![1, 2, 3]);
+	assert!(address::DecodeBase58("0OIl").is_none());
+
+	let (Body, Checksum) = Data.split_at(Data.len() - address::CHECKSUM_LENGTH);
+	assert_eq!(address::Checksum(Body), Checksum);
+
+	let (Prefix, _) = address::DecodeVarint(Body).unwrap();
+	assert!(address::PREFIXES.contains(&(Prefix, Network::Mainnet, AddressType::Standard)));
+}
+
+#[test]
+#[cfg(feature = "signing")]
+fn Test_SignedEncoding() {
+	let Key = b"shared secret";
+	let Code = MoneroRequest_Rust::EncodeSigned(&MinimalRequest(), Key).unwrap();
+	assert!(Code.starts_with("monero-request-signed:1:"));
+
+	let Output = MoneroRequest_Rust::DecodeSigned(&Code, Key).unwrap();
+	assert_eq!(Output.SellersWallet, MinimalRequest().SellersWallet);
+
+	// Wrong key, or an altered code
+	assert!(matches!(MoneroRequest_Rust::DecodeSigned(&Code, b"other secret"), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Signature does not match."))));
+	let (Code, Tag) = Code.rsplit_once(':').unwrap();
+	let Altered = MoneroRequest_Rust::EncodePaymentRequest(MoneroRequest { Amount: "100".to_string(), ..MinimalRequest() }).unwrap();
+	let Altered = format!("monero-request-signed{}:{}", &Altered["monero-request".len()..], Tag);
+	assert!(matches!(MoneroRequest_Rust::DecodeSigned(&Altered, Key), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Signature does not match."))));
+
+	// Plain codes aren't signed codes, and signed codes aren't standard ones
+	let Plain = format!("monero-request{}", &Code["monero-request-signed".len()..]);
+	assert!(MoneroRequest_Rust::DecodeSigned(&Plain, Key).is_err());
+	assert!(MoneroRequest_Rust::DecodePaymentRequest(format!("{Code}:{Tag}")).is_err());
+}
+
+#[test]
+fn Test_GenRandomPaymentIDWithPrefix() {
+	let Output = MoneroRequest_Rust::GenRandomPaymentIDWithPrefix("AC").unwrap();
+	assert_eq!(Output.len(), 16);
+	assert!(Output.starts_with("ac"));
+	assert!(MoneroRequest { PaymentID: Output, ..MinimalRequest() }.Check().is_ok());
+
+	assert_eq!(MoneroRequest_Rust::GenRandomPaymentIDWithPrefix("").unwrap().len(), 16);
+	assert!(MoneroRequest_Rust::GenRandomPaymentIDWithPrefix("deadbeef").unwrap().starts_with("deadbeef"));
+	assert!(matches!(MoneroRequest_Rust::GenRandomPaymentIDWithPrefix("deadbeef0"), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("PaymentID prefix is too long."))));
+	assert!(matches!(MoneroRequest_Rust::GenRandomPaymentIDWithPrefix("xyz"), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("PaymentID prefix is not hex."))));
+}
+
+#[test]
+fn Test_ContentHash() {
+	let Input = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), PaymentID: "0123456789abcdef".to_string(), ..MinimalRequest() };
+	let Hash = Input.ContentHash().unwrap();
+
+	// What normalizing fills in or rewrites doesn't change the hash
+	let Same = MoneroRequest { CustomLabel: "Monero Payment Request".to_string(), Currency: "xmr".to_string(), PaymentID: "0123456789ABCDEF".to_string(), StartDate: "2024-01-01T01:00:00+01:00".to_string(), Version: "1".to_string(), ..Input.clone() };
+	assert_eq!(Same.ContentHash().unwrap(), Hash);
+
+	assert_ne!(MoneroRequest { Amount: "2".to_string(), ..Input.clone() }.ContentHash().unwrap(), Hash);
+
+	// Empty ids and dates are hashed as empty, not as whatever normalizing would generate
+	assert_eq!(MinimalRequest().ContentHash().unwrap(), MinimalRequest().ContentHash().unwrap());
+
+	assert!(MoneroRequest { Currency: "EUR".to_string(), ..Input }.ContentHash().is_err());
+}
+
+#[test]
+fn Test_MaxNumberOfPayments() {
+	let Input = MoneroRequest { NumberOfPayments: 255, ..MinimalRequest() };
+	assert!(Input.Check().is_ok());
+
+	let Options = MoneroRequest_Rust::ValidationOptions { MaxNumberOfPayments: 12, ..Default::default() };
+	assert!(matches!(Input.CheckWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("NumberOfPayments exceeds maximum"))));
+	assert!(MoneroRequest { NumberOfPayments: 12, ..MinimalRequest() }.CheckWith(&Options).is_ok());
+}
+
+#[test]
+fn Test_PaymentScheduleIter() {
+	let Request = MoneroRequest { StartDate: "2024-01-01T00:00:00.000Z".to_string(), DaysPerBillingCycle: 30, NumberOfPayments: 3, ..MinimalRequest() };
+	assert_eq!(Request.PaymentScheduleIter().unwrap().collect::<Vec<_>>(), Request.PaymentSchedule().unwrap());
+
+	// Indefinite schedules never run out
+	let Indefinite = MoneroRequest { NumberOfPayments: 0, ..Request.clone() };
+	assert_eq!(Indefinite.PaymentScheduleIter().unwrap().nth(100).unwrap().to_rfc3339(), "2032-03-19T00:00:00+00:00");
+}
+
+#[test]
+fn Test_ToTemplateMap() {
+	let Input = MoneroRequest { CustomLabel: "Coffee".to_string(), Currency: "USD".to_string(), Amount: "1234.5".to_string(), StartDate: "2024-01-31T10:00:00.000Z".to_string(), NumberOfPayments: 12, ..MinimalRequest() };
+	let Output = Input.ToTemplateMap();
+
+	assert_eq!(Output.keys().copied().collect::<Vec<_>>(), vec!["amount", "change_indicator_url", "currency", "label", "payment_id", "payments", "start_date", "term", "wallet"]);
+	assert_eq!(Output["label"], "Coffee");
+	assert_eq!(Output["wallet"], "4At3X5…hCeX2S");
+	assert_eq!(Output["amount"], Input.DisplayAmount());
+	assert_eq!(Output["start_date"], "2024-01-31");
+	assert_eq!(Output["term"], "monthly");
+	assert_eq!(Output["payments"], "12");
+
+	let Output = MoneroRequest { Amount: "".to_string(), DaysPerBillingCycle: 14, NumberOfPayments: 0, ..MinimalRequest() }.ToTemplateMap();
+	assert_eq!((Output["label"].as_str(), Output["amount"].as_str(), Output["term"].as_str(), Output["payments"].as_str()), ("Monero Payment Request", "Any amount", "every 14 days", "ongoing"));
+	assert_eq!(MinimalRequest().ToTemplateMap()["term"], "one-time");
+}
+
+#[test]
+fn Test_BurnAddress() {
+	use MoneroRequest_Rust::address;
+	// A mainnet standard address with the given keys, with a valid checksum
+	let Address = |Spend: [u8; 32], View: [u8; 32]| {
+		let Body: Vec<u8> = [18u8].into_iter().chain(Spend).chain(View).collect();
+		address::EncodeBase58(&[Body.clone(), address::Checksum(&Body).to_vec()].concat())
+	};
+	let Identity = { let mut Key = [0u8; 32]; Key[0] = 1; Key };
+
+	for Burn in [Address([0; 32], [0; 32]), Address(Identity, Identity), Address([7; 32], [0; 32])] {
+		assert!(address::ParseWalletAddress(&Burn).is_ok());
+		let Output = MoneroRequest { SellersWallet: Burn, ..MinimalRequest() }.Check();
+		assert!(matches!(Output, Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Seller wallet is a known invalid/burn address"))));
+	}
+	assert!(MinimalRequest().Check().is_ok());
+
+	let Options = MoneroRequest_Rust::ValidationOptions { DeniedWallets: vec![MinimalRequest().SellersWallet], ..Default::default() };
+	assert!(matches!(MinimalRequest().CheckWith(&Options), Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Seller wallet is a known invalid/burn address"))));
+}
+
+#[test]
+#[cfg(feature = "async")]
+fn Test_DecodeStream() {
+	use futures::StreamExt;
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Input = format!("{Code}\r\n\n  {Code}  \n{}\nnot a code\n{Code}", "A".repeat(MoneroRequest_Rust::MAX_ENCODED_LENGTH + 100));
+
+	let Output: Vec<_> = futures::executor::block_on(MoneroRequest_Rust::DecodeStream(futures::io::Cursor::new(Input.into_bytes())).collect());
+
+	assert_eq!(Output.len(), 5);
+	assert!(Output[0].is_ok() && Output[1].is_ok() && Output[4].is_ok());
+	assert!(matches!(Output[2], Err(MoneroRequest_Rust::MoneroRequestError::InvalidInput("Encoded request is too long."))));
+	assert!(Output[3].is_err());
+}
+
+#[test]
+fn Test_Upgrade() {
+	let Code = MoneroRequest_Rust::EncodePaymentRequest(MinimalRequest()).unwrap();
+	let Upgraded = MoneroRequest_Rust::Upgrade(&Code).unwrap();
+
+	assert!(Upgraded.starts_with(&format!("monero-request:{}:", MoneroRequest_Rust::LATEST_VERSION)));
+	assert_eq!(MoneroRequest_Rust::DecodePaymentRequest(Upgraded).unwrap(), MoneroRequest_Rust::DecodePaymentRequest(Code).unwrap());
+
+	assert!(MoneroRequest_Rust::Upgrade("monero-request:99:abc").is_err());
+	assert!(MoneroRequest_Rust::Upgrade("not a code").is_err());
+}